@@ -1,5 +1,5 @@
 use itertools::Itertools; // 0.13.0
-use aoc24_util::{fetch_input, parse_as_columns};
+use aoc24_util::{fetch_input, parse_as_columns, report_answer};
 
 fn resolve(list1: &[usize], list2: &[usize]) -> usize {
     list1
@@ -14,5 +14,5 @@ fn resolve(list1: &[usize], list2: &[usize]) -> usize {
 fn main() {
     let input = fetch_input(1);
     let columns = parse_as_columns(&input);
-    println!("{}", resolve(&columns[0], &columns[1]));
+    report_answer(1, 1, resolve(&columns[0], &columns[1]));
 }
@@ -1,46 +1,16 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, invalid_cell, report_answer, unwrap_or_exit, Grid, Vec2d};
 use std::collections::HashSet;
 
-struct Map {
-    map: Vec<Option<Location>>,
-    width: usize,
-    height: usize,
-}
-
-impl Map {
-    fn parse(input: &str) -> Self {
-        let width = input.find('\n').unwrap();
-        let map: Vec<_> = input
-            .trim()
-            .split('\n')
-            .enumerate()
-            .map(|(y, s)| {
-                s.chars().enumerate().map(move |(x, c)| {
-                    if c == '.' {
-                        None
-                    } else {
-                        Some(Location::new(
-                            x as isize,
-                            y as isize,
-                            c.to_string().parse().unwrap(),
-                        ))
-                    }
-                })
-            })
-            .flatten()
-            .collect();
-        let height = map.len() / width;
+type Map = Grid<Option<Location>>;
 
-        Self { map, width, height }
-    }
-
-    fn get(&self, x: isize, y: isize) -> Option<Location> {
-        if x >= 0 && y >= 0 && x < self.width as isize && y < self.height as isize {
-            self.map[x as usize + y as usize * self.width]
-        } else {
-            None
-        }
-    }
+fn parse(input: &str) -> Map {
+    unwrap_or_exit(Grid::parse(input, |c, pos| match c {
+        '.' => Ok(None),
+        _ => match c.to_digit(10) {
+            Some(level) => Ok(Some(Location::new(pos.x, pos.y, level as usize))),
+            None => Err(invalid_cell(c, pos)),
+        },
+    }))
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -69,9 +39,8 @@ fn score(map: &Map, trailhead: Location) -> usize {
                         continue;
                     }
 
-                    if let Some(next_location) =
-                        map.get(current_location.x + xdiff, current_location.y + ydiff)
-                    {
+                    let next = Vec2d::new(current_location.x + xdiff, current_location.y + ydiff);
+                    if let Some(next_location) = map.get(next).copied().flatten() {
                         //print!("{:?} <- {:?}", next_location, current_location);
                         if next_location.level == current_location.level + 1 {
                             //println!("*");
@@ -92,8 +61,7 @@ fn score(map: &Map, trailhead: Location) -> usize {
 
 fn resolve(map: Map) -> usize {
     let trailheads: Vec<_> = map
-        .map
-        .iter()
+        .cells()
         .filter_map(|v| *v)
         .filter(|location| location.level == 0)
         .collect();
@@ -104,7 +72,7 @@ fn resolve(map: Map) -> usize {
 fn main() {
     let text = fetch_input(10);
 
-    let map = Map::parse(&text);
+    let map = parse(&text);
 
-    println!("{}", resolve(map));
+    report_answer(10, 1, resolve(map));
 }
@@ -1,6 +1,6 @@
 #![feature(linked_list_cursors)]
 
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer};
 use std::collections::LinkedList;
 
 fn blink(stones: &mut LinkedList<usize>) {
@@ -49,5 +49,5 @@ fn main() {
         blink(&mut stones);
     }
 
-    println!("{}", stones.len());
+    report_answer(11, 1, stones.len());
 }
@@ -1,45 +1,22 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{Dir4, Grid, Vec2d, fetch_input, report_answer, unwrap_or_exit};
 use std::collections::{HashMap, HashSet};
 
-struct Map {
-    map: Vec<Option<Plot>>,
-    width: isize,
-    height: isize,
-}
+type Map = Grid<Option<Plot>>;
 
-impl Map {
-    fn parse(text: &str) -> Self {
-        let map: Vec<_> = text
-            .trim()
-            .split('\n')
-            .enumerate()
-            .map(|(y, row)| {
-                row.chars()
-                    .enumerate()
-                    .map(move |(x, ch)| Some(Plot::new(x as isize, y as isize, ch)))
-            })
-            .flatten()
-            .collect();
-        let width = map.last().unwrap().unwrap().x + 1;
-        let height = map.last().unwrap().unwrap().y + 1;
-
-        Self { map, width, height }
-    }
+fn parse(text: &str) -> Map {
+    unwrap_or_exit(Grid::parse(text, |ch, pos| {
+        Ok(Some(Plot::new(pos.x, pos.y, ch)))
+    }))
+}
 
-    fn get(&self, id: Id) -> Option<Plot> {
-        let Id(x, y) = id;
+fn get(map: &Map, id: Id) -> Option<Plot> {
+    let Id(x, y) = id;
 
-        if x >= 0 && y >= 0 && x < self.width && y < self.height {
-            self.map[(x + y * self.width) as usize]
-        } else {
-            None
-        }
-    }
+    map.get(Vec2d::new(x, y)).copied().flatten()
+}
 
-    fn visit(&mut self, x: isize, y: isize) {
-        let w = self.width;
-        self.map[(x + y * w) as usize] = None;
-    }
+fn visit(map: &mut Map, x: isize, y: isize) {
+    map[Vec2d::new(x, y)] = None;
 }
 
 // Treat top-left-most plot location as region id
@@ -83,28 +60,29 @@ impl Region {
 fn find_regions(map: &mut Map) -> HashMap<Id, Region> {
     let mut regions = HashMap::new();
 
-    let (w, h) = (map.width, map.height);
-    for (x, y) in (0..h).map(|y| (0..w).map(move |x| (x, y))).flatten() {
-        if let Some(root_plot) = map.get(Id(x, y)) {
+    for Vec2d { x, y } in map.positions() {
+        if let Some(root_plot) = get(map, Id(x, y)) {
             let region = regions
                 .entry(root_plot.id())
                 .or_insert(Region::new(root_plot));
 
-            map.visit(x, y);
+            visit(map, x, y);
 
             let mut current_plots = vec![root_plot];
 
             while !current_plots.is_empty() {
                 for current_plot in std::mem::take(&mut current_plots) {
-                    for (xdiff, ydiff) in [(-1, 0), (1, 0), (0, 1), (0, -1)] {
-                        let neighbor_plot_id = Id(current_plot.x + xdiff, current_plot.y + ydiff);
+                    for dir in Dir4::ALL {
+                        let offset = dir.offset();
+                        let neighbor_plot_id =
+                            Id(current_plot.x + offset.x, current_plot.y + offset.y);
 
-                        if let Some(neighbor_plot) = map.get(neighbor_plot_id) {
+                        if let Some(neighbor_plot) = get(map, neighbor_plot_id) {
                             if neighbor_plot.plant == current_plot.plant {
                                 region.area += 1;
                                 region.plot_ids.insert(neighbor_plot.id());
 
-                                map.visit(neighbor_plot.x, neighbor_plot.y);
+                                visit(map, neighbor_plot.x, neighbor_plot.y);
 
                                 current_plots.push(neighbor_plot);
                             } else {
@@ -129,14 +107,14 @@ fn region_price(region: &Region) -> usize {
 }
 
 fn price(regions: &HashMap<Id, Region>) -> usize {
-    regions.iter().map(|(_, r)| region_price(r)).sum()
+    regions.values().map(region_price).sum()
 }
 
 fn main() {
     let text = fetch_input(12);
 
-    let mut map = Map::parse(&text);
+    let mut map = parse(&text);
     let regions = find_regions(&mut map);
 
-    println!("{}", price(&regions));
+    report_answer(12, 1, price(&regions));
 }
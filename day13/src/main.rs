@@ -1,16 +1,15 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{Vec2d, fetch_input, report_answer};
 use itertools::Itertools;
 use std::collections::HashMap;
-use std::ops::Sub;
 
 #[derive(Debug)]
 struct Button {
-    shift: Coord,
+    shift: Vec2d,
     cost: usize,
 }
 
 impl Button {
-    fn new(shift: Coord, cost: usize) -> Self {
+    fn new(shift: Vec2d, cost: usize) -> Self {
         Self { shift, cost }
     }
 }
@@ -27,32 +26,9 @@ impl Machine {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Coord {
-    x: isize,
-    y: isize,
-}
-
-impl Coord {
-    fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-}
-
-impl Sub for Coord {
-    type Output = Coord;
-
-    fn sub(self, other: Coord) -> Coord {
-        Coord {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
-}
-
 struct Context {
     machine: Machine,
-    cost_record: HashMap<Coord, usize>,
+    cost_record: HashMap<Vec2d, usize>,
 }
 
 impl Context {
@@ -90,15 +66,15 @@ impl Counter {
     }
 }
 
-fn min_cost_sub(ctx: &mut Context, coord: Coord, counter: Counter) -> usize {
+fn min_cost_sub(ctx: &mut Context, coord: Vec2d, counter: Counter) -> usize {
     let c = coord;
 
     if counter.a > 100 || counter.b > 100 {
-        return std::usize::MAX;
+        return usize::MAX;
     } else if c.x == 0 && c.y == 0 {
         return 0;
     } else if c.x < 0 || c.y < 0 {
-        return std::usize::MAX;
+        return usize::MAX;
     } else if let Some(existing) = ctx.cost_record.get(&c) {
         return *existing;
     }
@@ -115,34 +91,33 @@ fn min_cost_sub(ctx: &mut Context, coord: Coord, counter: Counter) -> usize {
     cost
 }
 
-fn min_cost(ctx: &mut Context, coord: Coord) -> usize {
+fn min_cost(ctx: &mut Context, coord: Vec2d) -> usize {
     min_cost_sub(ctx, coord, Counter::new())
 }
 
-fn parse_spec_line(text: &str, delim: char) -> (isize, isize) {
+fn parse_spec_line(text: &str, delim: char) -> Vec2d {
     text.split(':')
         .skip(1)
         .map(|shifts| {
             let (x, y) = shifts
                 .trim()
                 .split(',')
-                .map(|shift| {
+                .flat_map(|shift| {
                     shift
                         .split(delim)
                         .skip(1)
                         .map(|v| v.parse::<isize>().unwrap())
                 })
-                .flatten()
                 .collect_tuple()
                 .unwrap();
 
-            (x, y)
+            Vec2d::new(x, y)
         })
         .last()
         .unwrap()
 }
 
-fn parse(text: &str) -> Vec<(Machine, Coord)> {
+fn parse(text: &str) -> Vec<(Machine, Vec2d)> {
     let text: Vec<_> = text.split('\n').collect();
 
     text.chunks(4)
@@ -159,46 +134,34 @@ fn parse(text: &str) -> Vec<(Machine, Coord)> {
             let target = parse_spec_line(lines[2], '=');
 
             (
-                Machine::new(
-                    Button::new(Coord::new(button_a_spec.0, button_a_spec.1), 3),
-                    Button::new(Coord::new(button_b_spec.0, button_b_spec.1), 1),
-                ),
-                Coord::new(target.0, target.1),
+                Machine::new(Button::new(button_a_spec, 3), Button::new(button_b_spec, 1)),
+                target,
             )
         })
         .collect()
 }
 
-fn resolve(specs: Vec<(Machine, Coord)>) -> usize {
+fn resolve(specs: Vec<(Machine, Vec2d)>) -> usize {
     specs
         .into_iter()
         .map(|(machine, target)| {
             let mut ctx = Context::new(machine);
             min_cost(&mut ctx, target)
         })
-        .filter(|c| *c != std::usize::MAX)
+        .filter(|c| *c != usize::MAX)
         .sum()
 }
 
 fn main() {
     let text = fetch_input(13);
-    //     let text = "Button A: X+94, Y+34
-    // Button B: X+22, Y+67
-    // Prize: X=8400, Y=5400
-
-    // Button A: X+26, Y+66
-    // Button B: X+67, Y+21
-    // Prize: X=12748, Y=12176
-
-    // Button A: X+17, Y+86
-    // Button B: X+84, Y+37
-    // Prize: X=7870, Y=6450
+    let specs = parse(&text);
 
-    // Button A: X+69, Y+23
-    // Button B: X+27, Y+71
-    // Prize: X=18641, Y=10279";
+    report_answer(13, 1, resolve(specs));
+}
 
-    let specs = parse(&text);
+#[test]
+fn test_1() {
+    let text = aoc24_util::example(13, 1);
 
-    println!("{}", resolve(specs));
+    assert_eq!(480, resolve(parse(&text)));
 }
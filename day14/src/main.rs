@@ -1,18 +1,6 @@
-use aoc24_util::{fetch_input, Vec2d};
+use aoc24_util::{Torus, Vec2d, fetch_input, report_answer};
 use itertools::Itertools;
 
-struct Map {
-    size: Vec2d,
-}
-
-impl Map {
-    fn new(x: isize, y: isize) -> Self {
-        Self {
-            size: Vec2d::new(x, y),
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Robot {
     pos: Vec2d,
@@ -24,8 +12,8 @@ impl Robot {
         Self { pos, vel }
     }
 
-    fn step(&mut self, map: &Map) {
-        self.pos = ((self.pos + self.vel) + map.size) % map.size;
+    fn position_at(&self, map: &Torus, seconds: isize) -> Vec2d {
+        map.advance(self.pos, self.vel, seconds)
     }
 }
 
@@ -33,10 +21,9 @@ fn parse(text: &str) -> Vec<Robot> {
     let specs: Vec<Vec2d> = text
         .trim()
         .split('\n')
-        .map(|line| {
+        .flat_map(|line| {
             line.split_whitespace()
-                .map(|exp| exp.split('=').skip(1))
-                .flatten()
+                .flat_map(|exp| exp.split('=').skip(1))
                 .map(|value| {
                     Vec2d::from_tuple(
                         value
@@ -47,7 +34,6 @@ fn parse(text: &str) -> Vec<Robot> {
                     )
                 })
         })
-        .flatten()
         .collect();
 
     specs
@@ -58,34 +44,36 @@ fn parse(text: &str) -> Vec<Robot> {
         .collect()
 }
 
-fn resolve(mut robots: Vec<Robot>, map: Map) -> usize {
-    for _ in 0..100 {
-        for robot in &mut robots {
-            robot.step(&map);
-        }
-    }
-
-    let mut quadrants = vec![vec![0, 0], vec![0, 0]];
-    let center = map.size / 2;
+fn resolve(robots: Vec<Robot>, map: Torus, seconds: isize) -> usize {
+    let mut quadrants = [vec![0, 0], vec![0, 0]];
+    let center = map.size() / 2;
 
     for robot in &robots {
-        if robot.pos.x == center.x || robot.pos.y == center.y {
+        let pos = robot.position_at(&map, seconds);
+
+        if pos.x == center.x || pos.y == center.y {
             continue;
         }
 
-        quadrants[(robot.pos.x / (center.x + 1)) as usize]
-            [(robot.pos.y / (center.y + 1)) as usize] += 1;
+        quadrants[(pos.x / (center.x + 1)) as usize][(pos.y / (center.y + 1)) as usize] += 1;
     }
 
-    quadrants.iter().flatten().fold(1, |m, v| m * v)
+    quadrants.iter().flatten().product()
 }
 
 fn main() {
-    let map = Map::new(101, 103);
+    let map = Torus::new(101, 103);
     let text = fetch_input(14);
 
     let robots = parse(&text);
-    let safety_factor = resolve(robots, map);
+    let safety_factor = resolve(robots, map, 100);
+
+    report_answer(14, 1, safety_factor);
+}
+
+#[test]
+fn test_1() {
+    let text = aoc24_util::example(14, 1);
 
-    println!("{}", safety_factor);
+    assert_eq!(12, resolve(parse(&text), Torus::new(11, 7), 100));
 }
@@ -1,16 +1,16 @@
-use aoc24_util::{fetch_input, Vec2d};
+use aoc24_util::{Dir4, Grid, Vec2d, fetch_input, invalid_cell, report_answer, unwrap_or_exit};
 
 struct Map {
-    map: Vec<Vec<Option<Object>>>,
+    map: Grid<Option<Object>>,
 }
 
 impl Map {
     fn get(&self, p: Vec2d) -> Option<Object> {
-        self.map[p.y as usize][p.x as usize].clone()
+        self.map[p].clone()
     }
 
     fn set(&mut self, p: Vec2d, value: Option<Object>) {
-        self.map[p.y as usize][p.x as usize] = value;
+        self.map[p] = value;
     }
 
     fn move_object(&mut self, from: Vec2d, to: Vec2d) {
@@ -45,27 +45,19 @@ impl Map {
     fn score(&self) -> usize {
         self.map
             .iter()
-            .enumerate()
-            .map(|(y, row)| {
-                row.iter().enumerate().map(move |(x, v)| match v {
-                    Some(o) if o.ty == 'O' => y * 100 + x,
-                    _ => 0,
-                })
+            .map(|(pos, v)| match v {
+                Some(o) if o.ty == 'O' => (pos.y * 100 + pos.x) as usize,
+                _ => 0,
             })
-            .flatten()
             .sum()
     }
 
     fn debug(&self) {
-        for line in &self.map {
-            for obj in line {
-                match obj {
-                    Some(obj) => print!("{}", obj.ty),
-                    None => print!("."),
-                }
-            }
-            println!();
-        }
+        print!(
+            "{}",
+            self.map
+                .render(|obj| obj.as_ref().map_or('.', |obj| obj.ty))
+        );
     }
 }
 
@@ -94,18 +86,12 @@ impl Robot {
             return false;
         }
 
-        let direction = match self.instructions[self.index] {
-            '<' => (-1, 0),
-            '^' => (0, -1),
-            '>' => (1, 0),
-            'v' => (0, 1),
-            _ => unreachable!(),
-        };
+        let direction = Dir4::from_char(self.instructions[self.index]).unwrap();
 
-        let offset = direction.into();
+        let offset = direction.offset();
 
         if map.try_move_objects(self.pos, offset) {
-            self.pos = self.pos + offset;
+            self.pos += offset;
         }
 
         self.index += 1;
@@ -115,33 +101,18 @@ impl Robot {
 }
 
 fn parse(text: &str) -> (Map, Robot) {
+    let text = text.replace("\r\n", "\n");
     let mut sections = text.split("\n\n");
-    let mut robot_pos = Vec2d::new(0, 0);
-    let map = sections
-        .next()
-        .unwrap()
-        .split('\n')
-        .enumerate()
-        .map(|(y, line)| {
-            line.trim()
-                .chars()
-                .enumerate()
-                .map(|(x, v)| {
-                    let pos = (x as isize, y as isize).into();
-
-                    if v == '@' {
-                        robot_pos = pos;
-                    }
-
-                    match v {
-                        '.' => None,
-                        '#' | '@' | 'O' => Some(Object { ty: v }),
-                        v => unreachable!("{}", v),
-                    }
-                })
-                .collect()
-        })
-        .collect();
+    let (map, markers) = unwrap_or_exit(Grid::parse_with_markers(
+        sections.next().unwrap(),
+        "@",
+        |v, pos| match v {
+            '.' => Ok(None),
+            '#' | '@' | 'O' => Ok(Some(Object { ty: v })),
+            v => Err(invalid_cell(v, pos)),
+        },
+    ));
+    let robot_pos = markers.get('@').expect("no robot on the map");
     let instructions = sections
         .next()
         .unwrap()
@@ -153,7 +124,7 @@ fn parse(text: &str) -> (Map, Robot) {
 }
 
 fn resolve(text: &str) -> usize {
-    let (mut map, mut robot) = parse(&text);
+    let (mut map, mut robot) = parse(text);
 
     map.debug();
 
@@ -166,48 +137,19 @@ fn resolve(text: &str) -> usize {
 
 fn main() {
     let text = fetch_input(15);
-    println!("{}", resolve(&text));
+    report_answer(15, 1, resolve(&text));
 }
 
 #[test]
 fn test_1() {
-    let text = "##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
+    let text = aoc24_util::example(15, 1);
 
     assert_eq!(10092, resolve(&text));
 }
 
 #[test]
 fn test_2() {
-    let text = "########
-    #..O.O.#
-    ##@.O..#
-    #...O..#
-    #.#.O..#
-    #...O..#
-    #......#
-    ########
-
-<^^>>>vv<v>>v<<";
+    let text = aoc24_util::example(15, 2);
 
     assert_eq!(2028, resolve(&text));
 }
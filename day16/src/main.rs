@@ -1,194 +1,55 @@
-use aoc24_util::fetch_input;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use aoc24_util::{Dir4, Grid, Vec2d, dijkstra, fetch_input, report_answer, unwrap_or_exit};
 
 struct Map {
-    map: Vec<Vec<char>>,
+    map: Grid<char>,
 }
 
 impl Map {
     fn parse(text: &str) -> Self {
         Self {
-            map: text
-                .split('\n')
-                .map(|line| line.chars().collect())
-                .collect(),
+            map: unwrap_or_exit(Grid::parse_chars(text)),
         }
     }
 
-    fn find(&self, ch: char) -> Position {
-        self.map
-            .iter()
-            .enumerate()
-            .find_map(|(y, line)| {
-                line.iter().enumerate().find_map(move |(x, c)| {
-                    if *c == ch {
-                        Some(Position::new(x as isize, y as isize))
-                    } else {
-                        None
-                    }
-                })
-            })
-            .unwrap()
+    fn find(&self, ch: char) -> Vec2d {
+        self.map.position(&ch).unwrap()
     }
 
-    fn find_start(&self) -> Position {
+    fn find_start(&self) -> Vec2d {
         self.find('S')
     }
 
-    fn find_goal(&self) -> Position {
+    fn find_goal(&self) -> Vec2d {
         self.find('E')
     }
 
-    fn get(&self, x: isize, y: isize) -> Option<&char> {
-        self.map.get(y as usize).and_then(|l| l.get(x as usize))
+    fn visitable(&self, pos: Vec2d) -> bool {
+        self.map.get(pos).is_some_and(|c| *c != '#')
     }
-
-    fn visitable(&self, pos: Position) -> bool {
-        self.get(pos.x, pos.y).map(|c| *c != '#').unwrap_or(false)
-    }
-}
-
-#[derive(Debug, Clone, Eq)]
-struct State {
-    cost: usize,
-    pos: Position,
-    dir: Direction,
-    visited: HashSet<Position>,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
-    }
-}
-
-impl State {
-    fn new(pos: Position, dir: Direction) -> Self {
-        let mut visited = HashSet::new();
-
-        visited.insert(pos);
-
-        Self {
-            cost: 0,
-            pos,
-            dir,
-            visited,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-struct Position {
-    x: isize,
-    y: isize,
 }
 
-impl Position {
-    fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-}
+/// Where the reindeer stands and which way it faces.
+type State = (Vec2d, Dir4);
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
-enum Direction {
-    North,
-    West,
-    East,
-    South,
-}
-
-impl Direction {
-    fn as_offset(&self) -> Position {
-        match self {
-            Self::North => Position::new(0, -1),
-            Self::South => Position::new(0, 1),
-            Self::East => Position::new(1, 0),
-            Self::West => Position::new(-1, 0),
-        }
-    }
-
-    fn turn_cost(&self, to: Direction) -> usize {
-        match (self, to) {
-            (Self::North, Self::South)
-            | (Self::South, Self::North)
-            | (Self::East, Self::West)
-            | (Self::West, Self::East) => 2000,
-            (x, y) if *x == y => 0,
-            _ => 1000,
-        }
-    }
+fn successors(map: &Map, (pos, facing): State) -> Vec<(State, usize)> {
+    Dir4::ALL
+        .into_iter()
+        .map(|dir| (pos + dir.offset(), dir))
+        .filter(|(new_pos, _)| map.visitable(*new_pos))
+        .map(|(new_pos, dir)| ((new_pos, dir), facing.turns(dir) * 1000 + 1))
+        .collect()
 }
 
 fn resolve(map: &Map) -> usize {
-    let start = map.find_start();
     let goal = map.find_goal();
 
-    let mut cache = HashMap::new();
-    let mut heap = BinaryHeap::new();
-
-    heap.push(State::new(start, Direction::East));
-
-    while let Some(mut state) = heap.pop() {
-        if state.pos == goal {
-            return state.cost;
-        }
-
-        if let Some(existing_cost) = cache.get(&state.pos) {
-            if *existing_cost < state.cost {
-                continue;
-            }
-        }
-
-        cache.insert(state.pos, state.cost);
-
-        for dir in [
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ] {
-            let offset = dir.as_offset();
-
-            let new_pos = Position::new(state.pos.x + offset.x, state.pos.y + offset.y);
-
-            if !map.visitable(new_pos) {
-                // wall!
-                continue;
-            }
-
-            if state.visited.contains(&new_pos) {
-                // visited!
-                continue;
-            }
-            state.visited.insert(new_pos);
-
-            let mut forked_state = state.clone();
-
-            let turn_cost = state.dir.turn_cost(dir);
-            let move_cost = 1;
-
-            forked_state.pos = new_pos;
-            forked_state.cost = state.cost + turn_cost + move_cost;
-            forked_state.dir = dir;
-
-            heap.push(forked_state);
-        }
-    }
+    let route = dijkstra(
+        (map.find_start(), Dir4::East),
+        |state| successors(map, *state),
+        |(pos, _)| *pos == goal,
+    );
 
-    unreachable!("no path found")
+    route.expect("no path found").cost
 }
 
 fn main() {
@@ -198,28 +59,12 @@ fn main() {
 
     let cost = resolve(&map);
 
-    println!("{}", cost);
+    report_answer(16, 1, cost);
 }
 
 #[test]
 fn test_1() {
-    let text = "#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################";
+    let text = aoc24_util::example(16, 3);
 
     let map = Map::parse(&text);
     assert_eq!(resolve(&map), 11048);
@@ -227,21 +72,7 @@ fn test_1() {
 
 #[test]
 fn test_2() {
-    let text = "###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############";
+    let text = aoc24_util::example(16, 1);
 
     let map = Map::parse(&text);
     assert_eq!(resolve(&map), 7036);
@@ -1,4 +1,4 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer};
 use itertools::Itertools;
 
 #[derive(Debug, Clone)]
@@ -17,23 +17,23 @@ impl Cpu {
 
         let a = spec[0]
             .split(':')
-            .last()
+            .next_back()
             .map(|v| v.trim().parse().unwrap())
             .unwrap();
         let b = spec[1]
             .split(':')
-            .last()
+            .next_back()
             .map(|v| v.trim().parse().unwrap())
             .unwrap();
         let c = spec[2]
             .split(':')
-            .last()
+            .next_back()
             .map(|v| v.trim().parse().unwrap())
             .unwrap();
 
         let p = spec[4]
             .split(':')
-            .last()
+            .next_back()
             .map(|seq| {
                 seq.trim()
                     .split(',')
@@ -90,7 +90,7 @@ impl Cpu {
         let value = self.p[self.pc + 1];
 
         match value {
-            0 | 1 | 2 | 3 => value,
+            0..=3 => value,
             4 => self.a,
             5 => self.b,
             6 => self.c,
@@ -100,13 +100,13 @@ impl Cpu {
 
     fn adv(&mut self) {
         print!("adv: {} / 2.pow({})", self.a, self.read_combo());
-        self.a = self.a / (2usize.pow(self.read_combo() as u32));
+        self.a /= 2usize.pow(self.read_combo() as u32);
         println!("-> {}", self.a);
     }
 
     fn bxl(&mut self) {
         print!("bxl: {} ^ {}", self.b, self.read_literal());
-        self.b = self.b ^ self.read_literal();
+        self.b ^= self.read_literal();
         println!("-> {}", self.b);
     }
 
@@ -129,7 +129,7 @@ impl Cpu {
 
     fn bxc(&mut self) {
         print!("bxc: {} ^ {}", self.b, self.c);
-        self.b = self.b ^ self.c;
+        self.b ^= self.c;
         println!("-> {}", self.b);
     }
 
@@ -157,6 +157,7 @@ fn resolve1(text: &str) -> String {
     cpu.out.iter().map(|v| v.to_string()).join(",")
 }
 
+#[allow(dead_code)]
 fn resolve2(text: &str) -> usize {
     let cpu = Cpu::parse(text);
 
@@ -183,13 +184,9 @@ fn resolve2(text: &str) -> usize {
 
 #[test]
 fn test_1() {
-    let text = "Register A: 729
-Register B: 0
-Register C: 0
+    let text = aoc24_util::example(17, 1);
 
-Program: 0,1,5,4,3,0";
-
-    assert_eq!("4,6,3,5,6,3,5,2,1,0", resolve1(text));
+    assert_eq!("4,6,3,5,6,3,5,2,1,0", resolve1(&text));
 }
 
 #[test]
@@ -205,19 +202,15 @@ Program: 0,3,5,4,3,0";
 
 #[test]
 fn test_3() {
-    let text = "Register A: 2024
-Register B: 0
-Register C: 0
-
-Program: 0,3,5,4,3,0";
+    let text = aoc24_util::example(17, 2);
 
-    assert_eq!(117440, resolve2(text));
+    assert_eq!(117440, resolve2(&text));
 }
 
 fn main() {
     let text = fetch_input(17);
 
-    println!("part1: {}", resolve1(&text));
+    report_answer(17, 1, resolve1(&text));
 
-    // println!("part2: {}", resolve2(&text));
+    // report_answer(17, 2, resolve2(&text));
 }
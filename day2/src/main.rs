@@ -1,13 +1,11 @@
-use aoc24_util::{fetch_input, parse_as_rows};
+use aoc24_util::{fetch_input, parse_as_rows, report_answer};
 
 fn acceptable_range(v1: usize, v2: usize) -> bool {
     let diff = v1.abs_diff(v2);
-    diff >= 1 && diff <= 3
+    (1..=3).contains(&diff)
 }
 
 fn resolve(reports: Vec<Vec<usize>>) -> usize {
-    let items = [7, 6, 4, 2, 1];
-
     reports
         .iter()
         .filter(|row| {
@@ -26,5 +24,5 @@ fn main() {
     let text = fetch_input(2);
     let rows = parse_as_rows(&text);
     println!("{:#?}", rows);
-    println!("{}", resolve(rows));
+    report_answer(2, 1, resolve(rows));
 }
@@ -1,4 +1,4 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer};
 use regex::Regex;
 
 fn resolve(input: &str) -> usize {
@@ -11,5 +11,5 @@ fn resolve(input: &str) -> usize {
 
 fn main() {
     let input = fetch_input(3);
-    println!("{}", resolve(&input));
+    report_answer(3, 1, resolve(&input));
 }
@@ -1,4 +1,4 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
@@ -34,15 +34,18 @@ fn main() {
 
     let (rules, updates) = parse_rules_updates(&text);
 
-    println!(
-        "{}",
+    report_answer(
+        5,
+        1,
         updates
             .into_iter()
-            .map(|u| if evaluate(&u, &rules) {
-                u[u.len() / 2]
-            } else {
-                0
+            .map(|u| {
+                if evaluate(&u, &rules) {
+                    u[u.len() / 2]
+                } else {
+                    0
+                }
             })
-            .sum::<usize>()
+            .sum::<usize>(),
     );
 }
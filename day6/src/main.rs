@@ -1,42 +1,4 @@
-use aoc24_util::fetch_input;
-
-#[derive(Copy, Clone, Debug)]
-enum Orientation {
-    Up,
-    Right,
-    Left,
-    Down,
-}
-
-impl Orientation {
-    fn parse(ch: char) -> Self {
-        match ch {
-            '^' => Orientation::Up,
-            '>' => Orientation::Right,
-            '<' => Orientation::Left,
-            'v' => Orientation::Down,
-            _ => unreachable!("unexpected orientation"),
-        }
-    }
-
-    fn turn(self) -> Orientation {
-        match self {
-            Orientation::Up => Orientation::Right,
-            Orientation::Right => Orientation::Down,
-            Orientation::Down => Orientation::Left,
-            Orientation::Left => Orientation::Up,
-        }
-    }
-
-    fn new_pos(self, pos: (isize, isize)) -> (isize, isize) {
-        match self {
-            Orientation::Up => (pos.0, pos.1 - 1),
-            Orientation::Right => (pos.0 + 1, pos.1),
-            Orientation::Down => (pos.0, pos.1 + 1),
-            Orientation::Left => (pos.0 - 1, pos.1),
-        }
-    }
-}
+use aoc24_util::{fetch_input, invalid_cell, report_answer, unwrap_or_exit, Dir4, Grid, Vec2d};
 
 #[derive(Clone, Copy, Debug)]
 enum State {
@@ -45,69 +7,42 @@ enum State {
     Block,
 }
 
-#[derive(Debug)]
-struct Map {
-    map: Vec<Vec<State>>,
-}
-
-impl Map {
-    fn is_out_of_map(&self, pos: (isize, isize)) -> bool {
-        pos.0 < 0
-            || pos.1 < 0
-            || pos.1 >= self.map.len() as isize
-            || pos.0 >= self.map[0].len() as isize
-    }
-
-    fn can_proceed(&self, pos: (isize, isize)) -> bool {
-        !matches!(self.map[pos.1 as usize][pos.0 as usize], State::Block)
-    }
-
-    fn step(&mut self, pos: (isize, isize)) {
-        self.map[pos.1 as usize][pos.0 as usize] = State::Visited;
-    }
+type Map = Grid<State>;
 
-    fn count_visited(&self) -> usize {
-        self.map
-            .iter()
-            .map(|row| row.iter().filter(|v| matches!(v, State::Visited)).count())
-            .sum()
-    }
+fn count_visited(map: &Map) -> usize {
+    map.cells().filter(|v| matches!(v, State::Visited)).count()
+}
 
-    fn debug(&self) {
-        for row in &self.map {
-            for v in row {
-                let ch = match v {
-                    State::Open => '.',
-                    State::Visited => 'X',
-                    State::Block => '#',
-                };
-                print!("{}", ch);
-            }
-            println!();
-        }
-    }
+#[allow(dead_code)]
+fn debug(map: &Map) {
+    let text = map.render(|v| match v {
+        State::Open => '.',
+        State::Visited => 'X',
+        State::Block => '#',
+    });
+    print!("{}", text);
 }
 
 #[derive(Debug)]
 struct Guard {
-    pos: (isize, isize),
-    orientation: Orientation,
+    pos: Vec2d,
+    orientation: Dir4,
 }
 
 impl Guard {
     fn update(&mut self, map: &mut Map) -> bool {
-        map.step(self.pos);
-        
-        let new_pos = self.orientation.new_pos(self.pos);
+        map[self.pos] = State::Visited;
 
-        if map.is_out_of_map(new_pos) {
+        let new_pos = self.pos + self.orientation.offset();
+
+        let Some(next) = map.get(new_pos) else {
             return false;
-        }
+        };
 
-        if map.can_proceed(new_pos) {
+        if !matches!(next, State::Block) {
             self.pos = new_pos;
         } else {
-            self.orientation = self.orientation.turn();
+            self.orientation = self.orientation.turn_right();
         }
 
         true
@@ -115,57 +50,44 @@ impl Guard {
 }
 
 fn parse(text: &str) -> (Map, Guard) {
-    let mut guard = None;
-
-    let map = text
-        .split('\n')
-        .enumerate()
-        .map(|(y, s)| {
-            s.chars()
-                .enumerate()
-                .map(|(x, v)| match v {
-                    '#' => State::Block,
-                    '.' => State::Open,
-                    '<' | '>' | 'v' | '^' => {
-                        guard = Some(Guard {
-                            pos: (x as isize, y as isize),
-                            orientation: Orientation::parse(v),
-                        });
-
-                        State::Open
-                    }
-                    _ => unreachable!("unexpected input"),
-                })
-                .collect()
+    let (map, markers) = unwrap_or_exit(Grid::parse_with_markers(text, "^>v<", |v, pos| match v {
+        '#' => Ok(State::Block),
+        '.' | '<' | '>' | 'v' | '^' => Ok(State::Open),
+        _ => Err(invalid_cell(v, pos)),
+    }));
+
+    let guard = "^>v<"
+        .chars()
+        .find_map(|v| {
+            markers.get(v).map(|pos| Guard {
+                pos,
+                orientation: Dir4::from_char(v).unwrap(),
+            })
         })
-        .collect();
+        .expect("no guard on the map");
 
-    (Map { map }, guard.unwrap())
+    (map, guard)
 }
 
 fn simulate(input: &str) -> usize {
     let (mut map, mut guard) = parse(input);
 
     while guard.update(&mut map) {
-        // map.debug();
+        // debug(&map);
     }
 
-    map.count_visited()
+    count_visited(&map)
 }
 
 fn main() {
-    // let text = "....#.....
-// .........#
-// ..........
-// ..#.......
-// .......#..
-// ..........
-// .#..^.....
-// ........#.
-// #.........
-// ......#...";
-
     let text = fetch_input(6);
-    
-    println!("{}", simulate(&text));
+
+    report_answer(6, 1, simulate(&text));
+}
+
+#[test]
+fn test_1() {
+    let text = aoc24_util::example(6, 1);
+
+    assert_eq!(41, simulate(&text));
 }
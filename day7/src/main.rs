@@ -1,4 +1,4 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer};
 use itertools::Itertools;
 
 fn resolve(test_value: usize, operands: &[usize]) -> bool {
@@ -43,7 +43,7 @@ fn main() {
     let sum: usize = equations
         .iter()
         .map(|(test_value, operands)| {
-            if resolve(*test_value, &operands) {
+            if resolve(*test_value, operands) {
                 *test_value
             } else {
                 0
@@ -51,5 +51,5 @@ fn main() {
         })
         .sum();
 
-    println!("{}", sum);
+    report_answer(7, 1, sum);
 }
@@ -1,37 +1,30 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer, unwrap_or_exit, Grid, Vec2d};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-fn resolve(map: Vec<String>, map_size: (isize, isize)) -> usize {
-    let mut antennas: HashMap<char, Vec<(isize, isize)>> = HashMap::new();
+fn resolve(map: &Grid<char>) -> usize {
+    let mut antennas: HashMap<char, Vec<Vec2d>> = HashMap::new();
 
-    for (y, l) in map.iter().enumerate() {
-        for (x, ch) in l.chars().enumerate() {
-            if !ch.is_alphabetic() && !ch.is_digit(10) {
-                continue;
-            }
-
-            antennas
-                .entry(ch)
-                .or_insert_with(Vec::new)
-                .push((x as isize, y as isize));
+    for (pos, ch) in map.iter() {
+        if !ch.is_alphabetic() && !ch.is_ascii_digit() {
+            continue;
         }
+
+        antennas.entry(*ch).or_default().push(pos);
     }
 
     // println!("antennas: {:#?}", antennas);
 
     let antinodes: HashSet<_> = antennas
-        .iter()
-        .map(|(_, locations)| {
+        .values()
+        .flat_map(|locations| {
             locations.iter().permutations(2).map(|location_pair| {
-                let (x1, y1) = location_pair[0];
-                let (x2, y2) = location_pair[1];
+                let (a, b) = (*location_pair[0], *location_pair[1]);
 
-                (x2 + (x2 - x1), y2 + (y2 - y1))
+                b * 2 - a
             })
         })
-        .flatten()
-        .filter(|(x, y)| *x >= 0 && *y >= 0 && *x < map_size.0 && *y < map_size.1)
+        .filter(|pos| map.contains(*pos))
         .collect();
 
     antinodes.len()
@@ -40,8 +33,7 @@ fn resolve(map: Vec<String>, map_size: (isize, isize)) -> usize {
 fn main() {
     let text = fetch_input(8);
 
-    let map: Vec<String> = text.trim().split('\n').map(|s| s.into()).collect();
-    let map_size = (map[0].len() as isize, map.len() as isize);
+    let map = unwrap_or_exit(Grid::parse_chars(&text));
 
-    println!("{}", resolve(map, map_size));
+    report_answer(8, 1, resolve(&map));
 }
@@ -1,4 +1,4 @@
-use aoc24_util::fetch_input;
+use aoc24_util::{fetch_input, report_answer};
 
 enum Block {
     File(usize),
@@ -22,7 +22,7 @@ fn blocks(disk_map: &[usize]) -> impl DoubleEndedIterator<Item = Block> + use<'_
     disk_map
         .iter()
         .enumerate()
-        .map(|(i, v)| {
+        .flat_map(|(i, v)| {
             (0..*v).map(move |_| {
                 if i % 2 == 0 {
                     Block::File(i / 2)
@@ -31,7 +31,6 @@ fn blocks(disk_map: &[usize]) -> impl DoubleEndedIterator<Item = Block> + use<'_
                 }
             })
         })
-        .flatten()
 }
 
 fn resolve(disk_map: &[usize]) -> usize {
@@ -83,5 +82,5 @@ fn main() {
         .map(|v| v.to_string().parse().unwrap())
         .collect();
 
-    println!("{}", resolve(&disk_map));
+    report_answer(9, 1, resolve(&disk_map));
 }
@@ -0,0 +1,209 @@
+use crate::config::active_profile;
+use crate::error::{AocError, Result};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+pub const YEAR: usize = 2024;
+
+/// The event year, taken from the active profile or `AOC_YEAR` if set.
+pub fn year_from_env() -> Result<usize> {
+    match active_profile()?.and_then(|p| p.year) {
+        Some(year) => Ok(year),
+        None => year_from_env_var(),
+    }
+}
+
+/// The event year from `AOC_YEAR` alone, ignoring profiles.
+///
+/// Used for the example fixtures, so that day tests load the same files
+/// whatever the developer's config says.
+pub fn year_from_env_var() -> Result<usize> {
+    match std::env::var("AOC_YEAR") {
+        Ok(year) => parse_year(&year),
+        Err(_) => Ok(YEAR),
+    }
+}
+
+fn parse_year(year: &str) -> Result<usize> {
+    year.trim().parse().map_err(|_| AocError::Config {
+        path: None,
+        message: format!("AOC_YEAR must be a number, not `{}`", year),
+    })
+}
+
+/// On-disk store of puzzle inputs, one file per day and year.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+    year: usize,
+}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            year: YEAR,
+        }
+    }
+
+    pub fn with_year(mut self, year: usize) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Uses the active profile's cache directory or `AOC_CACHE_DIR` if set,
+    /// otherwise `inputs/` at the workspace root.
+    ///
+    /// With a profile, inputs are kept in a subdirectory named after it.
+    pub fn from_env() -> Result<Self> {
+        let profile = active_profile()?;
+        let dir = match profile.and_then(|p| p.cache_dir.clone()) {
+            Some(dir) => dir,
+            None => match std::env::var_os("AOC_CACHE_DIR") {
+                Some(dir) => dir.into(),
+                None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+            },
+        };
+
+        let cache = match profile {
+            Some(profile) => Self::new(dir.join(&profile.name)),
+            None => Self::new(dir),
+        };
+
+        Ok(cache.with_year(year_from_env()?))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    /// Directory holding everything stored for the event year.
+    pub fn year_dir(&self) -> PathBuf {
+        self.dir.join(self.year.to_string())
+    }
+
+    pub fn path(&self, day: usize) -> PathBuf {
+        self.year_dir().join(format!("day{}.txt", day))
+    }
+
+    pub fn load(&self, day: usize) -> Option<String> {
+        fs::read_to_string(self.path(day)).ok()
+    }
+
+    pub fn store(&self, day: usize, input: &str) -> io::Result<()> {
+        let path = self.path(day);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first so that an interrupted run never
+        // leaves a truncated input behind.
+        let tmp = path.with_extension("txt.tmp");
+        fs::write(&tmp, input)?;
+        fs::rename(&tmp, &path)
+    }
+
+    /// Returns `true` if there was a cached input to remove.
+    pub fn remove(&self, day: usize) -> io::Result<bool> {
+        match fs::remove_file(self.path(day)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Removes the cached inputs of the year, leaving anything else in its directory.
+    pub fn clear(&self) -> io::Result<()> {
+        let entries = match fs::read_dir(self.year_dir()) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            let is_input = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("day"))
+                .and_then(|name| name.strip_suffix(".txt"))
+                .is_some_and(|day| day.parse::<usize>().is_ok());
+
+            if is_input {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_store_and_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+
+    assert_eq!(None, cache.load(1));
+
+    cache.store(1, "1 2\n3 4\n").unwrap();
+
+    assert_eq!(Some("1 2\n3 4\n".to_string()), cache.load(1));
+    assert_eq!(None, cache.load(2));
+}
+
+#[test]
+fn test_store_overwrites() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+
+    cache.store(3, "old").unwrap();
+    cache.store(3, "new").unwrap();
+
+    assert_eq!(Some("new".to_string()), cache.load(3));
+}
+
+#[test]
+fn test_years_are_separate() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+
+    let old = cache.clone().with_year(2023);
+    old.store(1, "old event").unwrap();
+
+    assert_eq!(None, cache.load(1));
+    assert_eq!(Some("old event".to_string()), old.load(1));
+}
+
+#[test]
+fn test_remove_and_clear() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+
+    cache.store(1, "a").unwrap();
+    cache.store(2, "b").unwrap();
+
+    assert!(cache.remove(1).unwrap());
+    assert!(!cache.remove(1).unwrap());
+    assert_eq!(Some("b".to_string()), cache.load(2));
+
+    std::fs::write(cache.year_dir().join("ledger.tsv"), "kept").unwrap();
+
+    cache.clear().unwrap();
+    cache.clear().unwrap();
+
+    assert_eq!(None, cache.load(2));
+    assert!(cache.year_dir().join("ledger.tsv").exists());
+}
+
+#[test]
+fn test_parse_year() {
+    assert_eq!(2015, parse_year("2015").unwrap());
+    assert_eq!(2023, parse_year(" 2023\n").unwrap());
+    assert!(matches!(parse_year("abc"), Err(AocError::Config { .. })));
+}
@@ -0,0 +1,337 @@
+use crate::cache::{year_from_env, YEAR};
+use crate::config::active_profile;
+use crate::error::{AocError, Result};
+use crate::throttle::Throttle;
+use reqwest::blocking::{self, RequestBuilder, Response};
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, USER_AGENT};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Retries of a GET request that failed with a 5xx status or a connection error.
+///
+/// POST requests, such as answer submissions, are never retried: the server may
+/// have acted on one that timed out.
+pub const RETRIES: u32 = 3;
+
+/// Delay before the first retry, doubled for each of the next ones.
+pub const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Talks to adventofcode.com, or any server serving the same paths.
+///
+/// Every request sends a User-Agent naming this crate and, if set, a contact.
+#[derive(Debug, Clone)]
+pub struct AocClient {
+    http: blocking::Client,
+    base_url: String,
+    year: usize,
+    session: Option<String>,
+    contact: Option<String>,
+    throttle: Option<Throttle>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl Default for AocClient {
+    fn default() -> Self {
+        Self::new(BASE_URL, YEAR)
+    }
+}
+
+impl AocClient {
+    /// `base_url` is the scheme and host, e.g. `http://127.0.0.1:8080`.
+    pub fn new<S: Into<String>>(base_url: S, year: usize) -> Self {
+        Self {
+            http: blocking::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            year,
+            session: None,
+            contact: None,
+            throttle: None,
+            retries: RETRIES,
+            retry_delay: RETRY_DELAY,
+        }
+    }
+
+    /// Uses the active profile, then `AOC_BASE_URL`, `AOC_YEAR` and `AOC_CONTACT` if set.
+    ///
+    /// The session comes from the profile too, or from `AOC_SESSION` on every request.
+    /// Requests are spaced out by the [shared](Throttle::shared) throttle.
+    pub fn from_env() -> Result<Self> {
+        let profile = active_profile()?;
+        let base_url = match profile.and_then(|p| p.base_url.clone()) {
+            Some(url) => url,
+            None => std::env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.into()),
+        };
+        let mut client = Self::new(base_url, year_from_env()?).with_throttle(Throttle::shared());
+
+        if let Some(contact) = profile
+            .and_then(|p| p.contact.clone())
+            .or_else(|| std::env::var("AOC_CONTACT").ok())
+        {
+            client = client.with_contact(contact);
+        }
+
+        Ok(match profile.and_then(|p| p.session.clone()) {
+            Some(session) => client.with_session(session),
+            None => client,
+        })
+    }
+
+    pub fn with_year(mut self, year: usize) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Sends `token` instead of reading `AOC_SESSION` on every request.
+    pub fn with_session<S: Into<String>>(mut self, token: S) -> Self {
+        self.session = Some(token.into());
+        self
+    }
+
+    /// Adds `contact`, e.g. a repository URL or an email address, to the User-Agent.
+    pub fn with_contact<S: Into<String>>(mut self, contact: S) -> Self {
+        self.contact = Some(contact.into());
+        self
+    }
+
+    /// Waits for `throttle` before every request, including retries.
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Retries transient failures `retries` times, waiting `delay`, then twice as long each time.
+    pub fn with_retries(mut self, retries: u32, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    /// URL of `path` within the event, e.g. `day/1/input`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}/{}", self.base_url, self.year, path)
+    }
+
+    /// Sends a GET request, failing unless the response is successful.
+    pub fn get(&self, path: &str) -> Result<Response> {
+        check_status(self.get_unchecked(path)?)
+    }
+
+    /// Sends a GET request and returns the response whatever its status is.
+    pub fn get_unchecked(&self, path: &str) -> Result<Response> {
+        self.send(|http| http.get(self.url(path)), true)
+    }
+
+    /// Sends a form POST request once, failing unless the response is successful.
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<Response> {
+        check_status(self.send(|http| http.post(self.url(path)).form(form), false)?)
+    }
+
+    /// The User-Agent sent with every request.
+    pub fn user_agent(&self) -> String {
+        let name = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+        match &self.contact {
+            Some(contact) => format!("{} (+{})", name, contact),
+            None => name.to_string(),
+        }
+    }
+
+    /// Sends the request built by `request`, throttled and, if `retry` is set,
+    /// retried on transient failures.
+    fn send<F>(&self, request: F, retry: bool) -> Result<Response>
+    where
+        F: Fn(&blocking::Client) -> RequestBuilder,
+    {
+        let headers = self.headers()?;
+        let mut attempt = 0;
+
+        loop {
+            if let Some(throttle) = &self.throttle {
+                throttle.wait()?;
+            }
+
+            let result = request(&self.http).headers(headers.clone()).send();
+            let transient = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+
+            if !retry || !transient || attempt == self.retries {
+                return Ok(result?);
+            }
+
+            std::thread::sleep(self.retry_delay * 2u32.pow(attempt));
+            attempt += 1;
+        }
+    }
+
+    fn headers(&self) -> Result<HeaderMap> {
+        let session_token = match &self.session {
+            Some(token) => token.clone(),
+            None => std::env::var("AOC_SESSION").map_err(|_| AocError::MissingSession)?,
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            USER_AGENT,
+            HeaderValue::from_str(&self.user_agent()).map_err(|_| AocError::Config {
+                path: None,
+                message: "the contact can't be sent in a User-Agent".into(),
+            })?,
+        );
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("session={}", session_token))
+                .map_err(|_| AocError::InvalidSession)?,
+        );
+        Ok(headers)
+    }
+}
+
+fn check_status(response: Response) -> Result<Response> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(status_error(&response))
+    }
+}
+
+/// The error for a response that wasn't successful.
+pub(crate) fn status_error(response: &Response) -> AocError {
+    let status = response.status();
+
+    if matches!(
+        status,
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+    ) {
+        // The site answers 400 when the session cookie is missing or stale.
+        AocError::ExpiredSession
+    } else {
+        AocError::Http {
+            url: response.url().to_string(),
+            status: status.as_u16(),
+        }
+    }
+}
+
+#[test]
+fn test_url() {
+    let client = AocClient::new("http://localhost:8080/", 2015);
+
+    assert_eq!(
+        "http://localhost:8080/2015/day/1/input",
+        client.url("day/1/input")
+    );
+}
+
+#[test]
+fn test_user_agent() {
+    use crate::mock::{MockResponse, MockServer};
+
+    let server = MockServer::start(|req| {
+        MockResponse::ok(req.header("user-agent").unwrap_or_default().to_string())
+    });
+    let client = AocClient::new(server.url(), 2023).with_session("abc");
+
+    let agent = client.get("day/1/input").unwrap().text().unwrap();
+    assert!(agent.starts_with("aoc24-util/"));
+
+    let client = client.with_contact("me@example.com");
+    let agent = client.get("day/1/input").unwrap().text().unwrap();
+    assert!(agent.ends_with(" (+me@example.com)"));
+}
+
+#[test]
+fn test_retries_server_errors() {
+    use crate::mock::{MockResponse, MockServer};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+        if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+            MockResponse::new(503, "Service Unavailable")
+        } else {
+            MockResponse::ok("1 2\n")
+        }
+    });
+    let client = AocClient::new(server.url(), 2023)
+        .with_session("abc")
+        .with_retries(3, Duration::from_millis(10));
+
+    assert_eq!("1 2\n", client.get("day/1/input").unwrap().text().unwrap());
+    assert_eq!(3, server.hits());
+
+    let server = MockServer::fixed(503, "Service Unavailable");
+    let client = AocClient::new(server.url(), 2023)
+        .with_session("abc")
+        .with_retries(1, Duration::from_millis(10));
+
+    assert!(matches!(
+        client.get("day/1/input"),
+        Err(AocError::Http { status: 503, .. })
+    ));
+    assert_eq!(2, server.hits());
+}
+
+#[test]
+fn test_never_retries_posts() {
+    let server = crate::mock::MockServer::fixed(503, "Service Unavailable");
+    let client = AocClient::new(server.url(), 2023)
+        .with_session("abc")
+        .with_retries(3, Duration::from_millis(10));
+
+    assert!(matches!(
+        client.post_form("day/1/answer", &[("level", "1"), ("answer", "42")]),
+        Err(AocError::Http { status: 503, .. })
+    ));
+    assert_eq!(1, server.hits());
+}
+
+#[test]
+fn test_retries_connection_errors() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let start = std::time::Instant::now();
+    let client = AocClient::new(url, 2023)
+        .with_session("abc")
+        .with_retries(2, Duration::from_millis(50));
+
+    assert!(matches!(
+        client.get("day/1/input"),
+        Err(AocError::Network(_))
+    ));
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+#[test]
+fn test_throttled_requests() {
+    let server = crate::mock::MockServer::fixed(200, "ok");
+    let dir = tempfile::tempdir().unwrap();
+    let client = AocClient::new(server.url(), 2023)
+        .with_session("abc")
+        .with_throttle(Throttle::new(
+            dir.path().join("lock"),
+            Duration::from_millis(100),
+        ));
+
+    let start = std::time::Instant::now();
+    for _ in 0..3 {
+        client.get("day/1/input").unwrap();
+    }
+
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(3, server.hits());
+}
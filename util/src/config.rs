@@ -0,0 +1,269 @@
+use crate::error::{AocError, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Settings read from `config.toml`, holding one profile per account.
+///
+/// ```toml
+/// default_profile = "alice"
+///
+/// [profiles.alice]
+/// session = "53616c74..."
+/// year = 2024
+/// cache_dir = "~/aoc/inputs"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// One account. Unset fields fall back to the environment variables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    pub session: Option<String>,
+    pub year: Option<usize>,
+    pub cache_dir: Option<PathBuf>,
+    pub base_url: Option<String>,
+    /// Added to the User-Agent so that the site can reach the owner of the script.
+    pub contact: Option<String>,
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut config: Config = toml::from_str(text).map_err(|e| AocError::Config {
+            path: None,
+            message: e.message().to_string(),
+        })?;
+
+        for (name, profile) in &mut config.profiles {
+            profile.name = name.clone();
+        }
+
+        Ok(config)
+    }
+
+    /// Reads the file at `path`, or returns an empty config if there is none.
+    ///
+    /// Relative and `~/` cache directories are resolved against the file's
+    /// directory and the home directory.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut config = Self::parse(&text).map_err(|e| match e {
+            AocError::Config { message, .. } => AocError::Config {
+                path: Some(path.to_path_buf()),
+                message,
+            },
+            e => e,
+        })?;
+
+        let base = path.parent().unwrap_or(Path::new("."));
+        for profile in config.profiles.values_mut() {
+            if let Some(dir) = &profile.cache_dir {
+                profile.cache_dir = Some(resolve_path(base, dir));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// `AOC_CONFIG` if set, otherwise `aoc24/config.toml` in the user config directory.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("AOC_CONFIG") {
+            return Some(path.into());
+        }
+
+        let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+        };
+
+        Some(dir.join("aoc24").join("config.toml"))
+    }
+
+    pub fn from_env() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The profile called `name`, or the default one if `name` is `None`.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<&Profile>> {
+        match name.or(self.default_profile.as_deref()) {
+            Some(name) => match self.profiles.get(name) {
+                Some(profile) => Ok(Some(profile)),
+                None => Err(AocError::Config {
+                    path: Self::path(),
+                    message: format!("no profile named `{}`", name),
+                }),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+fn resolve_path(base: &Path, path: &Path) -> PathBuf {
+    if let (Ok(rest), Some(home)) = (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        PathBuf::from(home).join(rest)
+    } else {
+        base.join(path)
+    }
+}
+
+/// The profile picked by `--profile <name>`, then `AOC_PROFILE`, then the config's default.
+///
+/// Read once per process; a broken config file or an unknown profile is an error
+/// every time.
+pub fn active_profile() -> Result<Option<&'static Profile>> {
+    static PROFILE: OnceLock<Result<Option<Profile>>> = OnceLock::new();
+
+    let profile = PROFILE.get_or_init(|| {
+        let name =
+            profile_arg(std::env::args().skip(1)).or_else(|| std::env::var("AOC_PROFILE").ok());
+
+        Config::from_env().and_then(|config| Ok(config.profile(name.as_deref())?.cloned()))
+    });
+
+    match profile {
+        Ok(profile) => Ok(profile.as_ref()),
+        Err(AocError::Config { path, message }) => Err(AocError::Config {
+            path: path.clone(),
+            message: message.clone(),
+        }),
+        Err(e) => Err(AocError::Config {
+            path: Config::path(),
+            message: e.to_string(),
+        }),
+    }
+}
+
+fn profile_arg<I>(args: I) -> Option<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            return args.next();
+        } else if let Some(name) = arg.strip_prefix("--profile=") {
+            return Some(name.to_string());
+        }
+    }
+
+    None
+}
+
+/// `args` without the `--profile <name>` and `--profile=<name>` read by [`active_profile`],
+/// so that they don't get in the way of the other arguments.
+pub fn without_profile_arg<I>(args: I) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let mut rest = vec![];
+
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            args.next();
+        } else if !arg.starts_with("--profile=") {
+            rest.push(arg);
+        }
+    }
+
+    rest
+}
+
+#[cfg(test)]
+const CONFIG: &str = r#"
+default_profile = "alice"
+
+[profiles.alice]
+session = "aaa"
+year = 2023
+
+[profiles.bob]
+session = "bbb"
+cache_dir = "bob-inputs"
+"#;
+
+#[test]
+fn test_parse_config() {
+    let config = Config::parse(CONFIG).unwrap();
+
+    let alice = config.profile(None).unwrap().unwrap();
+    assert_eq!("alice", alice.name);
+    assert_eq!(Some("aaa".into()), alice.session);
+    assert_eq!(Some(2023), alice.year);
+    assert_eq!(None, alice.cache_dir);
+
+    let bob = config.profile(Some("bob")).unwrap().unwrap();
+    assert_eq!(Some("bbb".into()), bob.session);
+    assert_eq!(None, bob.year);
+
+    assert!(matches!(
+        config.profile(Some("carol")),
+        Err(AocError::Config { .. })
+    ));
+    assert_eq!(None, Config::default().profile(None).unwrap());
+}
+
+#[test]
+fn test_load_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+
+    assert!(Config::load(&path).unwrap().profiles.is_empty());
+
+    fs::write(&path, CONFIG).unwrap();
+    let config = Config::load(&path).unwrap();
+    assert_eq!(
+        Some(dir.path().join("bob-inputs")),
+        config.profiles["bob"].cache_dir
+    );
+
+    fs::write(&path, "[profiles.alice]\nsesion = \"typo\"\n").unwrap();
+    assert!(matches!(
+        Config::load(&path),
+        Err(AocError::Config { path: Some(p), .. }) if p == path
+    ));
+}
+
+#[test]
+fn test_profile_arg() {
+    let args = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(None, profile_arg(args(&["--input", "a.txt"])));
+    assert_eq!(Some("bob".into()), profile_arg(args(&["--profile", "bob"])));
+    assert_eq!(Some("bob".into()), profile_arg(args(&["--profile=bob"])));
+
+    assert_eq!(
+        args(&["ledger", "3"]),
+        without_profile_arg(args(&["--profile", "bob", "ledger", "3"]))
+    );
+    assert_eq!(
+        args(&["vault", "seal", "1"]),
+        without_profile_arg(args(&["vault", "seal", "--profile=bob", "1"]))
+    );
+    assert_eq!(
+        args(&["ledger"]),
+        without_profile_arg(args(&["ledger", "--profile"]))
+    );
+}
@@ -0,0 +1,211 @@
+use crate::Vec2d;
+
+/// One of the four directions of a grid, with north pointing to smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Self::ALL.into_iter()
+    }
+
+    /// Reads an arrow (`^>v<`), a compass letter (`NESW`) or a move (`UDLR`).
+    pub fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '^' | 'N' | 'U' => Some(Dir4::North),
+            '>' | 'E' | 'R' => Some(Dir4::East),
+            'v' | 'S' | 'D' => Some(Dir4::South),
+            '<' | 'W' | 'L' => Some(Dir4::West),
+            _ => None,
+        }
+    }
+
+    /// Position in [`Dir4::ALL`].
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Fewest quarter turns from `self` to `to`: 0, 1 or 2.
+    pub fn turns(self, to: Dir4) -> usize {
+        let diff = (to.index() + 4 - self.index()) % 4;
+        diff.min(4 - diff)
+    }
+
+    /// The step to the neighbor in this direction.
+    pub fn offset(self) -> Vec2d {
+        match self {
+            Dir4::North => Vec2d::new(0, -1),
+            Dir4::East => Vec2d::new(1, 0),
+            Dir4::South => Vec2d::new(0, 1),
+            Dir4::West => Vec2d::new(-1, 0),
+        }
+    }
+}
+
+impl From<Dir4> for Vec2d {
+    fn from(dir: Dir4) -> Self {
+        dir.offset()
+    }
+}
+
+/// One of the eight directions of a grid, diagonals included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Self::ALL.into_iter()
+    }
+
+    /// Reads the same characters as [`Dir4::from_char`], giving the straight directions.
+    pub fn from_char(ch: char) -> Option<Self> {
+        Dir4::from_char(ch).map(Self::from)
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Self::ALL[index % 8]
+    }
+
+    /// Turns by 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// Turns by 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// Fewest 45 degree turns from `self` to `to`: 0 to 4.
+    pub fn turns(self, to: Dir8) -> usize {
+        let diff = (to.index() + 8 - self.index()) % 8;
+        diff.min(8 - diff)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The step to the neighbor in this direction.
+    pub fn offset(self) -> Vec2d {
+        match self {
+            Dir8::North => Vec2d::new(0, -1),
+            Dir8::NorthEast => Vec2d::new(1, -1),
+            Dir8::East => Vec2d::new(1, 0),
+            Dir8::SouthEast => Vec2d::new(1, 1),
+            Dir8::South => Vec2d::new(0, 1),
+            Dir8::SouthWest => Vec2d::new(-1, 1),
+            Dir8::West => Vec2d::new(-1, 0),
+            Dir8::NorthWest => Vec2d::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::from_index(dir.index() * 2)
+    }
+}
+
+impl From<Dir8> for Vec2d {
+    fn from(dir: Dir8) -> Self {
+        dir.offset()
+    }
+}
+
+#[test]
+fn test_dir4() {
+    assert_eq!(Dir4::East, Dir4::North.turn_right());
+    assert_eq!(Dir4::West, Dir4::North.turn_left());
+    assert_eq!(Dir4::North, Dir4::West.turn_right());
+    assert_eq!(Dir4::South, Dir4::North.reverse());
+
+    assert_eq!(0, Dir4::East.turns(Dir4::East));
+    assert_eq!(1, Dir4::East.turns(Dir4::North));
+    assert_eq!(1, Dir4::North.turns(Dir4::West));
+    assert_eq!(2, Dir4::West.turns(Dir4::East));
+
+    assert_eq!(
+        Vec2d::new(0, 0),
+        Dir4::iter().fold(Vec2d::new(0, 0), |pos, dir| pos + dir.offset())
+    );
+    assert_eq!(Vec2d::new(-1, 0), Dir4::West.into());
+}
+
+#[test]
+fn test_dir4_from_char() {
+    let parse = |s: &str| s.chars().map(Dir4::from_char).collect::<Option<Vec<_>>>();
+
+    assert_eq!(Some(Dir4::ALL.to_vec()), parse("^>v<"));
+    assert_eq!(Some(Dir4::ALL.to_vec()), parse("NESW"));
+    assert_eq!(Some(Dir4::ALL.to_vec()), parse("URDL"));
+    assert_eq!(None, Dir4::from_char('x'));
+}
+
+#[test]
+fn test_dir8() {
+    assert_eq!(Dir8::NorthEast, Dir8::North.turn_right());
+    assert_eq!(Dir8::NorthWest, Dir8::North.turn_left());
+    assert_eq!(Dir8::SouthWest, Dir8::NorthEast.reverse());
+    assert_eq!(4, Dir8::NorthWest.turns(Dir8::SouthEast));
+    assert_eq!(3, Dir8::North.turns(Dir8::SouthWest));
+
+    assert_eq!(Dir8::South, Dir4::South.into());
+    assert_eq!(Some(Dir8::West), Dir8::from_char('<'));
+    assert!(Dir8::SouthEast.is_diagonal() && !Dir8::East.is_diagonal());
+    assert_eq!(Vec2d::new(1, 1), Dir8::SouthEast.offset());
+    assert_eq!(4, Dir8::iter().filter(|d| d.is_diagonal()).count());
+}
@@ -0,0 +1,175 @@
+use crate::ledger::Rejection;
+use std::fmt::{self, Display};
+use std::io;
+use std::path::PathBuf;
+
+/// Everything that can go wrong while fetching, parsing or submitting.
+#[derive(Debug)]
+pub enum AocError {
+    /// `AOC_SESSION` isn't set.
+    MissingSession,
+    /// The session token can't be sent as a cookie.
+    InvalidSession,
+    /// The site refused the session token, most likely because it expired.
+    ExpiredSession,
+    /// The site sent a web page where the puzzle input was expected.
+    HtmlPage,
+    /// The site asked not to request the input again before the puzzle unlocks.
+    RepeatedRequest,
+    /// The puzzle of the day hasn't unlocked yet.
+    NotUnlocked {
+        day: usize,
+    },
+    /// The request never got a response.
+    Network(reqwest::Error),
+    /// The site answered with an unexpected status.
+    Http {
+        url: String,
+        status: u16,
+    },
+    Io(io::Error),
+    /// The config file can't be read, or names a profile it doesn't have.
+    Config {
+        path: Option<PathBuf>,
+        message: String,
+    },
+    /// A vault file can't be decrypted, most likely because the key is wrong.
+    Vault {
+        path: Option<PathBuf>,
+        message: String,
+    },
+    /// The server sent JSON that doesn't have the expected shape.
+    Json(serde_json::Error),
+    /// A token of the input couldn't be parsed. Lines and columns start at 1.
+    Parse {
+        line: usize,
+        column: usize,
+        token: String,
+    },
+    /// A row of a grid doesn't have as many cells as the first one. Lines start at 1.
+    RaggedRow {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// The ledger refused to submit the answer.
+    Rejected(Rejection),
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::MissingSession => write!(
+                f,
+                "missing AOC_SESSION environment variable; set it to your adventofcode.com session cookie, or pick a profile with a session"
+            ),
+            AocError::InvalidSession => {
+                write!(f, "AOC_SESSION contains characters that can't be sent in a cookie")
+            }
+            AocError::ExpiredSession => write!(
+                f,
+                "the session cookie was rejected; log in again and update AOC_SESSION"
+            ),
+            AocError::HtmlPage => write!(
+                f,
+                "got a web page instead of the puzzle input; check AOC_SESSION and AOC_BASE_URL"
+            ),
+            AocError::RepeatedRequest => write!(
+                f,
+                "the site asked not to request this input again until the puzzle unlocks"
+            ),
+            AocError::NotUnlocked { day } => write!(f, "day {} hasn't unlocked yet", day),
+            AocError::Network(e) => write!(f, "couldn't reach the server: {}", e),
+            AocError::Http { url, status } => {
+                write!(f, "request to {} failed with status {}", url, status)
+            }
+            AocError::Io(e) => write!(f, "{}", e),
+            AocError::Config {
+                path: Some(path),
+                message,
+            } => write!(f, "invalid config {}: {}", path.display(), message),
+            AocError::Config {
+                path: None,
+                message,
+            } => write!(f, "invalid config: {}", message),
+            AocError::Vault {
+                path: Some(path),
+                message,
+            } => write!(f, "couldn't open {}: {}", path.display(), message),
+            AocError::Vault {
+                path: None,
+                message,
+            } => write!(f, "vault: {}", message),
+            AocError::Json(e) => write!(f, "unexpected JSON from the server: {}", e),
+            AocError::Parse {
+                line,
+                column,
+                token,
+            } => write!(
+                f,
+                "couldn't parse `{}` at line {}, column {}",
+                token, line, column
+            ),
+            AocError::RaggedRow {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "line {} has {} cells instead of {}",
+                line, width, expected
+            ),
+            AocError::Rejected(rejection) => write!(f, "not submitted, {}", rejection),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Network(e) => Some(e),
+            AocError::Io(e) => Some(e),
+            AocError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<reqwest::Error> for AocError {
+    fn from(e: reqwest::Error) -> Self {
+        AocError::Network(e)
+    }
+}
+
+impl From<serde_json::Error> for AocError {
+    fn from(e: serde_json::Error) -> Self {
+        AocError::Json(e)
+    }
+}
+
+impl From<Rejection> for AocError {
+    fn from(rejection: Rejection) -> Self {
+        AocError::Rejected(rejection)
+    }
+}
+
+/// Unwraps `result`, or prints the error and exits instead of panicking.
+///
+/// Meant for day binaries, where a backtrace doesn't help.
+pub fn unwrap_or_exit<T>(result: Result<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::cache::{year_from_env_var, YEAR};
+use crate::client::AocClient;
+use crate::error::{AocError, Result};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// Example blocks from the puzzle descriptions, stored as `<year>/day<N>/<K>.txt`.
+///
+/// Unlike inputs, examples are meant to be committed so that day tests can load them.
+#[derive(Debug, Clone)]
+pub struct ExampleStore {
+    dir: PathBuf,
+    year: usize,
+}
+
+impl ExampleStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            year: YEAR,
+        }
+    }
+
+    pub fn with_year(mut self, year: usize) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Uses `AOC_EXAMPLES_DIR` if set, otherwise `examples/` at the workspace root.
+    ///
+    /// The year comes from `AOC_YEAR` only, never from a profile.
+    pub fn from_env() -> Result<Self> {
+        let store = match std::env::var_os("AOC_EXAMPLES_DIR") {
+            Some(dir) => Self::new(dir),
+            None => Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples")),
+        };
+
+        Ok(store.with_year(year_from_env_var()?))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    pub fn day_dir(&self, day: usize) -> PathBuf {
+        self.dir
+            .join(self.year.to_string())
+            .join(format!("day{}", day))
+    }
+
+    /// Path of the `n`th example of the day, counting from 1.
+    pub fn path(&self, day: usize, n: usize) -> PathBuf {
+        self.day_dir(day).join(format!("{}.txt", n))
+    }
+
+    pub fn load(&self, day: usize, n: usize) -> Result<String> {
+        let path = self.path(day, n);
+
+        fs::read_to_string(&path).map_err(|e| {
+            AocError::Io(io::Error::new(
+                e.kind(),
+                format!(
+                    "couldn't read example {} of day {} from {}: {}",
+                    n,
+                    day,
+                    path.display(),
+                    e
+                ),
+            ))
+        })
+    }
+
+    /// Replaces the stored examples of the day with `examples`, numbered from 1.
+    pub fn store(&self, day: usize, examples: &[String]) -> io::Result<()> {
+        fs::create_dir_all(self.day_dir(day))?;
+
+        for (i, example) in examples.iter().enumerate() {
+            fs::write(self.path(day, i + 1), example)?;
+        }
+
+        // Drop leftovers from a page that used to have more blocks.
+        for n in examples.len() + 1.. {
+            match fs::remove_file(self.path(day, n)) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl AocClient {
+    /// The HTML description of the day's puzzle.
+    pub fn puzzle_page(&self, day: usize) -> Result<String> {
+        Ok(self.get(&format!("day/{}", day))?.text()?)
+    }
+}
+
+/// Text of every `<pre><code>` block of a puzzle page, in page order.
+///
+/// Markup inside the blocks, such as `<em>`, is dropped and entities are decoded.
+pub fn extract_examples(html: &str) -> Vec<String> {
+    let mut examples = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+
+        let Some(end) = rest.find("</code></pre>") else {
+            break;
+        };
+
+        examples.push(decode_entities(&strip_tags(&rest[..end])));
+        rest = &rest[end..];
+    }
+
+    examples
+}
+
+/// `html` with every tag removed, entities left as they are.
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Fetches the puzzle page of the day and stores its examples, returning how many there were.
+///
+/// They are stored under the year the page was fetched for.
+pub fn download_examples(day: usize) -> Result<usize> {
+    let client = AocClient::from_env()?;
+    let examples = extract_examples(&client.puzzle_page(day)?);
+
+    ExampleStore::from_env()?
+        .with_year(client.year())
+        .store(day, &examples)?;
+    Ok(examples.len())
+}
+
+/// Stores the examples of a puzzle page, returning how many there were.
+pub fn save_examples(day: usize, html: &str) -> Result<usize> {
+    let examples = extract_examples(html);
+    ExampleStore::from_env()?.store(day, &examples)?;
+    Ok(examples.len())
+}
+
+/// The `n`th stored example of the day, counting from 1.
+///
+/// Meant for day tests, so it panics with the reason when the example is missing.
+pub fn example(day: usize, n: usize) -> String {
+    ExampleStore::from_env()
+        .and_then(|store| store.load(day, n))
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[cfg(test)]
+const PAGE: &str = r#"<article class="day-desc"><h2>--- Day 3: Example ---</h2>
+<p>For example:</p>
+<pre><code>xmul(2,4)&amp;mul[3,7]!^don't()_mul(5,5)
+</code></pre>
+<p>Only <code>mul(2,4)</code> counts, and then:</p>
+<pre><code>#..<em>O</em>.
+a &lt; b &gt; c
+</code></pre>
+</article>"#;
+
+#[test]
+fn test_extract_examples() {
+    assert_eq!(
+        vec![
+            "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)\n".to_string(),
+            "#..O.\na < b > c\n".to_string(),
+        ],
+        extract_examples(PAGE)
+    );
+}
+
+#[test]
+fn test_store_examples() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ExampleStore::new(dir.path()).with_year(2023);
+
+    store
+        .store(3, &["one".into(), "two".into(), "three".into()])
+        .unwrap();
+    store.store(3, &["uno".into(), "dos".into()]).unwrap();
+
+    assert_eq!(dir.path().join("2023/day3/2.txt"), store.path(3, 2));
+    assert_eq!("uno", store.load(3, 1).unwrap());
+    assert_eq!("dos", store.load(3, 2).unwrap());
+    assert!(matches!(
+        store.load(3, 3),
+        Err(AocError::Io(e)) if e.kind() == ErrorKind::NotFound
+    ));
+}
+
+#[test]
+fn test_puzzle_page() {
+    use crate::mock::{MockResponse, MockServer};
+
+    let server = MockServer::start(|req| {
+        if req.path == "/2023/day/3" {
+            MockResponse::ok(PAGE)
+        } else {
+            MockResponse::new(404, "Not Found")
+        }
+    });
+    let client = AocClient::new(server.url(), 2023).with_session("abc");
+
+    assert_eq!(2, extract_examples(&client.puzzle_page(3).unwrap()).len());
+}
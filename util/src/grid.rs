@@ -0,0 +1,698 @@
+use crate::error::{AocError, Result};
+use crate::{Rect, Vec2d};
+use std::collections::BTreeMap;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid of cells stored row by row, indexed by [`Vec2d`].
+///
+/// `x` grows to the right and `y` downwards, with `(0, 0)` at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_vec(width, height, vec![value; width * height])
+    }
+
+    /// A `width` by `height` grid taking each cell from `source(pos)` of this one.
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Self
+    where
+        F: Fn(Vec2d) -> Vec2d,
+    {
+        let cells = (0..width * height)
+            .map(|i| self[source(pos_at(width, i))].clone())
+            .collect();
+
+        Self::from_vec(width, height, cells)
+    }
+
+    /// Rows become columns: the cell at `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |p| Vec2d::new(p.y, p.x))
+    }
+
+    /// Turned a quarter clockwise, so the first row becomes the last column.
+    pub fn rotate_90(&self) -> Self {
+        let h = self.height as isize;
+        self.remap(self.height, self.width, |p| Vec2d::new(p.y, h - 1 - p.x))
+    }
+
+    /// Turned upside down.
+    pub fn rotate_180(&self) -> Self {
+        let (w, h) = (self.width as isize, self.height as isize);
+        self.remap(self.width, self.height, |p| {
+            Vec2d::new(w - 1 - p.x, h - 1 - p.y)
+        })
+    }
+
+    /// Turned a quarter counterclockwise, so the first row becomes the first column.
+    pub fn rotate_270(&self) -> Self {
+        let w = self.width as isize;
+        self.remap(self.height, self.width, |p| Vec2d::new(w - 1 - p.y, p.x))
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width as isize;
+        self.remap(self.width, self.height, |p| Vec2d::new(w - 1 - p.x, p.y))
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height as isize;
+        self.remap(self.width, self.height, |p| Vec2d::new(p.x, h - 1 - p.y))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Wraps cells listed row by row. Panics unless there are `width * height` of them.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from its rows. Panics if they don't all have the same length.
+    pub fn from_rows<I, R>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row);
+            let len = cells.len() - before;
+
+            match width {
+                None => width = Some(len),
+                Some(width) => assert_eq!(
+                    width, len,
+                    "row {} has {} cells instead of {}",
+                    height, len, width
+                ),
+            }
+            height += 1;
+        }
+
+        Self::from_vec(width.unwrap_or(0), height, cells)
+    }
+
+    /// Parses a character map, turning each character and its position into a cell.
+    ///
+    /// Lines may end with `\n` or `\r\n`, and trailing empty lines are ignored.
+    /// Fails on the first error of `f`, or on a row whose length differs from the first.
+    pub fn parse<F>(text: &str, f: F) -> Result<Self>
+    where
+        F: FnMut(char, Vec2d) -> Result<T>,
+    {
+        Self::parse_with_markers(text, "", f).map(|(grid, _)| grid)
+    }
+
+    /// Like [`Grid::parse`], also recording where each of the `markers` characters
+    /// (such as `S` and `E`) appear.
+    pub fn parse_with_markers<F>(text: &str, markers: &str, mut f: F) -> Result<(Self, Markers)>
+    where
+        F: FnMut(char, Vec2d) -> Result<T>,
+    {
+        let mut cells = vec![];
+        let mut found = Markers::default();
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in text.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let before = cells.len();
+
+            for (x, ch) in line.chars().enumerate() {
+                let pos = Vec2d::new(x as isize, y as isize);
+
+                if markers.contains(ch) {
+                    found.positions.entry(ch).or_default().push(pos);
+                }
+                cells.push(f(ch, pos)?);
+            }
+
+            let len = cells.len() - before;
+            match width {
+                None => width = Some(len),
+                Some(expected) if expected != len => {
+                    return Err(AocError::RaggedRow {
+                        line: y + 1,
+                        width: len,
+                        expected,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok((Self::from_vec(width.unwrap_or(0), height, cells), found))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The positions of the grid, from `(0, 0)` to `(width, height)`.
+    pub fn rect(&self) -> Rect {
+        Rect::from_size(
+            Vec2d::ZERO,
+            Vec2d::new(self.width as isize, self.height as isize),
+        )
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Vec2d) -> bool {
+        self.rect().contains(pos)
+    }
+
+    fn index_of(&self, pos: Vec2d) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.x as usize + pos.y as usize * self.width)
+    }
+
+    /// The cell at `pos`, or `None` outside the grid.
+    pub fn get(&self, pos: Vec2d) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2d) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2d> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| pos_at(width, i))
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2d, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, and there are no rows to yield anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Cells from `start`, moving by `step` until the line leaves the grid.
+    fn line(&self, start: Vec2d, step: Vec2d) -> impl Iterator<Item = &T> {
+        std::iter::successors(Some(start), move |p| Some(*p + step)).map_while(|p| self.get(p))
+    }
+
+    /// Every diagonal going down and to the right, from the bottom left corner
+    /// to the top right one.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (w, h) = (self.width as isize, self.height as isize);
+        let starts = (0..h).rev().map(|y| Vec2d::new(0, y));
+        let starts = starts.chain((1..w).map(|x| Vec2d::new(x, 0)));
+
+        starts.map(|start| self.line(start, Vec2d::new(1, 1)))
+    }
+
+    /// Every diagonal going down and to the left, from the top left corner to the
+    /// bottom right one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (w, h) = (self.width as isize, self.height as isize);
+        let starts = (0..w).map(|x| Vec2d::new(x, 0));
+        let starts = starts.chain((1..h).map(move |y| Vec2d::new(w - 1, y)));
+
+        starts.map(|start| self.line(start, Vec2d::new(-1, 1)))
+    }
+
+    /// A borrowed window on the cells inside `rect`, or `None` if it goes past the grid.
+    pub fn view(&self, rect: Rect) -> Option<GridView<'_, T>> {
+        let inside = rect.is_empty() || self.rect().intersect(rect) == Some(rect);
+
+        inside.then_some(GridView { grid: self, rect })
+    }
+
+    /// Every `width` by `height` window, row by row, for matching patterns.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        let size = Vec2d::new(width as isize, height as isize);
+        let starts = Rect::new(Vec2d::ZERO, self.rect().max - size + Vec2d::new(1, 1));
+
+        starts.positions().map(move |min| GridView {
+            grid: self,
+            rect: Rect::from_size(min, size),
+        })
+    }
+
+    /// The first cell, row by row, matching `predicate`.
+    pub fn find<P>(&self, mut predicate: P) -> Option<(Vec2d, &T)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, cell)| predicate(cell))
+    }
+
+    /// Position of the first cell equal to `value`.
+    pub fn position(&self, value: &T) -> Option<Vec2d>
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .position(|cell| cell == value)
+            .map(|i| pos_at(self.width, i))
+    }
+
+    /// A grid of the same size with `f` applied to each cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Consumes the grid, turning each cell and its position into a new cell.
+    pub fn transform<U, F>(self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Vec2d, T) -> U,
+    {
+        let width = self.width;
+        let cells = self
+            .cells
+            .into_iter()
+            .enumerate()
+            .map(|(i, cell)| f(pos_at(width, i), cell))
+            .collect();
+
+        Grid::from_vec(self.width, self.height, cells)
+    }
+}
+
+/// A rectangle of a [`Grid`], with positions relative to its top left corner.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// Where the view lies in the grid.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn width(&self) -> usize {
+        self.rect.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rect.height()
+    }
+
+    pub fn get(&self, pos: Vec2d) -> Option<&'a T> {
+        let pos = pos + self.rect.min;
+
+        if self.rect.contains(pos) {
+            self.grid.get(pos)
+        } else {
+            None
+        }
+    }
+
+    /// Every cell with its position in the view, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2d, &'a T)> {
+        let (grid, min) = (self.grid, self.rect.min);
+
+        self.rect.positions().map(move |p| (p - min, &grid[p]))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &'a T> {
+        self.iter().map(|(_, cell)| cell)
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_vec(self.width(), self.height(), self.cells().cloned().collect())
+    }
+}
+
+impl<T> Index<Vec2d> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2d) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is out of a {}x{} view",
+                pos,
+                self.width(),
+                self.height()
+            ),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// The characters of a map as they are.
+    pub fn parse_chars(text: &str) -> Result<Self> {
+        Self::parse(text, |ch, _| Ok(ch))
+    }
+}
+
+/// The error for a character that doesn't belong in a map, at `pos`.
+pub fn invalid_cell(ch: char, pos: Vec2d) -> AocError {
+    AocError::Parse {
+        line: pos.y as usize + 1,
+        column: pos.x as usize + 1,
+        token: ch.to_string(),
+    }
+}
+
+/// Positions of the marker characters found by [`Grid::parse_with_markers`], row by row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers {
+    positions: BTreeMap<char, Vec<Vec2d>>,
+}
+
+impl Markers {
+    /// The first position of `marker`.
+    pub fn get(&self, marker: char) -> Option<Vec2d> {
+        self.all(marker).first().copied()
+    }
+
+    /// Every position of `marker`.
+    pub fn all(&self, marker: char) -> &[Vec2d] {
+        self.positions.get(&marker).map_or(&[], |p| p.as_slice())
+    }
+}
+
+/// Position of the cell stored at `index` in a grid `width` cells wide.
+fn pos_at(width: usize, index: usize) -> Vec2d {
+    Vec2d::new((index % width) as isize, (index / width) as isize)
+}
+
+/// Consumes the grid, yielding every cell with its position, row by row.
+impl<T> IntoIterator for Grid<T> {
+    type Item = (Vec2d, T);
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            cells: self.cells.into_iter().enumerate(),
+            width: self.width,
+        }
+    }
+}
+
+/// The iterator returned by [`Grid::into_iter`].
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    cells: std::iter::Enumerate<std::vec::IntoIter<T>>,
+    width: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = (Vec2d, T);
+
+    fn next(&mut self) -> Option<(Vec2d, T)> {
+        self.cells
+            .next()
+            .map(|(i, cell)| (pos_at(self.width, i), cell))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl<T> Index<Vec2d> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2d) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is out of a {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Vec2d> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2d) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is out of a {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+fn sample() -> Grid<char> {
+    Grid::from_rows(["abc".chars(), "def".chars()])
+}
+
+#[test]
+fn test_get() {
+    let mut grid = sample();
+
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(Some(&'a'), grid.get(Vec2d::new(0, 0)));
+    assert_eq!(Some(&'f'), grid.get(Vec2d::new(2, 1)));
+    assert_eq!(None, grid.get(Vec2d::new(3, 0)));
+    assert_eq!(None, grid.get(Vec2d::new(0, -1)));
+    assert!(!grid.contains(Vec2d::new(-1, 1)));
+    assert_eq!(Rect::new(Vec2d::ZERO, Vec2d::new(3, 2)), grid.rect());
+
+    *grid.get_mut(Vec2d::new(1, 1)).unwrap() = 'E';
+    grid[Vec2d::new(0, 1)] = 'D';
+    assert_eq!('E', grid[Vec2d::new(1, 1)]);
+    assert_eq!("abcDEf", grid.cells().collect::<String>());
+}
+
+#[test]
+#[should_panic(expected = "out of a 3x2 grid")]
+fn test_index_out_of_grid() {
+    let _ = sample()[Vec2d::new(0, 2)];
+}
+
+#[test]
+#[should_panic(expected = "row 1 has 2 cells instead of 3")]
+fn test_ragged_rows() {
+    Grid::from_rows(["abc".chars(), "de".chars()]);
+}
+
+#[test]
+fn test_rows_and_columns() {
+    let grid = sample();
+
+    assert_eq!(
+        vec![&['a', 'b', 'c'][..], &['d', 'e', 'f']],
+        grid.rows().collect::<Vec<_>>()
+    );
+    assert_eq!("cf", grid.column(2).collect::<String>());
+    assert_eq!(
+        vec!["ad", "be", "cf"],
+        grid.columns()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            Vec2d::new(0, 0),
+            Vec2d::new(1, 0),
+            Vec2d::new(2, 0),
+            Vec2d::new(0, 1)
+        ],
+        grid.positions().take(4).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_find() {
+    let grid = sample();
+
+    assert_eq!(Some(Vec2d::new(1, 1)), grid.position(&'e'));
+    assert_eq!(None, grid.position(&'z'));
+    assert_eq!(Some((Vec2d::new(2, 0), &'c')), grid.find(|c| *c > 'b'));
+}
+
+#[test]
+fn test_parse() {
+    let (grid, markers) =
+        Grid::parse_with_markers("S.#\r\n.#E\r\n\r\n", "SE", |ch, pos| match ch {
+            '#' => Ok(false),
+            '.' | 'S' | 'E' => Ok(true),
+            _ => Err(invalid_cell(ch, pos)),
+        })
+        .unwrap();
+
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(
+        vec![true, true, false, true, false, true],
+        grid.cells().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(Some(Vec2d::new(0, 0)), markers.get('S'));
+    assert_eq!(&[Vec2d::new(2, 1)], markers.all('E'));
+    assert_eq!(None, markers.get('@'));
+
+    assert_eq!(sample(), Grid::parse_chars("abc\ndef\n").unwrap());
+    assert!(Grid::parse_chars("").unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        Grid::parse_chars("abc\nabc\nab\n"),
+        Err(AocError::RaggedRow {
+            line: 3,
+            width: 2,
+            expected: 3
+        })
+    ));
+
+    let result = Grid::parse("..\n.x\n", |ch, pos| match ch {
+        '.' => Ok(()),
+        _ => Err(invalid_cell(ch, pos)),
+    });
+    match result {
+        Err(AocError::Parse {
+            line,
+            column,
+            token,
+        }) => assert_eq!((2, 2, "x"), (line, column, token.as_str())),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_rotate_and_flip() {
+    let grid = sample();
+    let text = |g: Grid<char>| {
+        g.rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    assert_eq!("ad/be/cf", text(grid.transpose()));
+    assert_eq!("da/eb/fc", text(grid.rotate_90()));
+    assert_eq!("fed/cba", text(grid.rotate_180()));
+    assert_eq!("cf/be/ad", text(grid.rotate_270()));
+    assert_eq!("cba/fed", text(grid.flip_horizontal()));
+    assert_eq!("def/abc", text(grid.flip_vertical()));
+    assert_eq!(grid, grid.rotate_90().rotate_270());
+    assert_eq!(grid.rotate_180(), grid.rotate_90().rotate_90());
+}
+
+#[test]
+fn test_diagonals() {
+    let grid = sample();
+    let lines = |lines: Vec<String>| lines.join("/");
+
+    assert_eq!(
+        "d/ae/bf/c",
+        lines(grid.diagonals().map(|d| d.collect()).collect())
+    );
+    assert_eq!(
+        "a/bd/ce/f",
+        lines(grid.anti_diagonals().map(|d| d.collect()).collect())
+    );
+}
+
+#[test]
+fn test_views() {
+    let grid = Grid::parse_chars("abcd\nefgh\nijkl\n").unwrap();
+
+    let view = grid
+        .view(Rect::from_size(Vec2d::new(1, 1), Vec2d::new(2, 2)))
+        .unwrap();
+    assert_eq!((2, 2), (view.width(), view.height()));
+    assert_eq!('f', view[Vec2d::new(0, 0)]);
+    assert_eq!(Some(&'k'), view.get(Vec2d::new(1, 1)));
+    assert_eq!(None, view.get(Vec2d::new(2, 0)));
+    assert_eq!("fgjk", view.cells().collect::<String>());
+    assert_eq!(Grid::parse_chars("fg\njk\n").unwrap(), view.to_grid());
+
+    assert!(grid
+        .view(Rect::from_size(Vec2d::new(3, 0), Vec2d::new(2, 1)))
+        .is_none());
+
+    let windows: Vec<String> = grid.windows(3, 2).map(|w| w.cells().collect()).collect();
+    assert_eq!(vec!["abcefg", "bcdfgh", "efgijk", "fghjkl"], windows);
+    assert_eq!(0, grid.windows(5, 1).count());
+}
+
+#[test]
+fn test_map_and_transform() {
+    let grid = sample();
+
+    let upper = grid.map(|c| c.to_ascii_uppercase());
+    assert_eq!("ABCDEF", upper.cells().collect::<String>());
+
+    let sums = grid.transform(|pos, _| pos.x + pos.y);
+    assert_eq!(
+        vec![0, 1, 2, 1, 2, 3],
+        sums.cells().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(Grid::new(3, 2, 0), sums.map(|_| 0));
+}
@@ -0,0 +1,342 @@
+use crate::cache::InputCache;
+use crate::client::AocClient;
+use crate::error::{AocError, Result};
+use crate::unlock::{format_duration, unlock_ts};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// The site asks not to fetch a leaderboard more often than this.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard, as served by `leaderboard/private/view/<id>.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users have no name.
+    pub name: Option<String>,
+    pub stars: usize,
+    pub local_score: u64,
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// Stars by day, then by part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<usize, BTreeMap<usize, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of the moment the star was earned.
+    pub get_star_ts: i64,
+    pub star_index: u64,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Members by decreasing local score, ties broken by stars then id.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|m| (std::cmp::Reverse((m.local_score, m.stars)), m.id));
+        members
+    }
+
+    /// Days at least one member has a star for, in order.
+    pub fn days(&self) -> Vec<usize> {
+        let mut days: Vec<_> = self
+            .members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys().copied())
+            .collect();
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    /// The standings table, then each day's finish times counted from the unlock.
+    pub fn standings(&self) -> String {
+        let year = self.event.parse().unwrap_or(0);
+        let ranking = self.ranking();
+        let width = ranking
+            .iter()
+            .map(|m| m.display_name().chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{:>3} {:>5} {:>5}  name", "#", "score", "stars");
+        for (rank, m) in ranking.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{:>3} {:>5} {:>5}  {}",
+                rank + 1,
+                m.local_score,
+                m.stars,
+                m.display_name()
+            );
+        }
+
+        for day in self.days() {
+            let _ = writeln!(out, "\nday {}", day);
+
+            for m in &ranking {
+                let Some(part1) = m.star_ts(day, 1) else {
+                    continue;
+                };
+                let unlock = unlock_ts(year, day);
+
+                let _ = write!(
+                    out,
+                    "  {:<width$}  {:>10}",
+                    m.display_name(),
+                    format_duration(part1 - unlock)
+                );
+                if let Some(part2) = m.star_ts(day, 2) {
+                    let _ = write!(
+                        out,
+                        "  {:>10}  (+{})",
+                        format_duration(part2 - unlock),
+                        format_duration(part2 - part1)
+                    );
+                }
+                out.push('\n');
+            }
+        }
+
+        out
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star_ts(&self, day: usize, part: usize) -> Option<i64> {
+        Some(self.completion_day_level.get(&day)?.get(&part)?.get_star_ts)
+    }
+}
+
+impl AocClient {
+    /// The raw JSON of a private leaderboard, always downloaded.
+    pub fn leaderboard_json(&self, id: u64) -> Result<String> {
+        let body = self
+            .get(&format!("leaderboard/private/view/{}.json", id))?
+            .text()?;
+
+        // Without a valid session the site redirects to its login page.
+        if body.trim_start().starts_with('<') {
+            return Err(AocError::ExpiredSession);
+        }
+
+        Ok(body)
+    }
+}
+
+/// Fetches private leaderboards, reusing a cached copy younger than the polling limit.
+#[derive(Debug, Clone)]
+pub struct LeaderboardSource {
+    client: AocClient,
+    cache: InputCache,
+    max_age: Duration,
+}
+
+impl LeaderboardSource {
+    pub fn new(client: AocClient, cache: InputCache) -> Self {
+        Self {
+            client,
+            cache,
+            max_age: POLL_INTERVAL,
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(AocClient::from_env()?, InputCache::from_env()?))
+    }
+
+    /// Reuses cached copies younger than `max_age` instead of [`POLL_INTERVAL`].
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn path(&self, id: u64) -> PathBuf {
+        self.cache
+            .year_dir()
+            .join(format!("leaderboard-{}.json", id))
+    }
+
+    /// The cached JSON if it is recent enough.
+    fn cached(&self, id: u64) -> Option<String> {
+        let path = self.path(id);
+        let age = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| SystemTime::now().duration_since(t).ok())?;
+
+        if age < self.max_age {
+            fs::read_to_string(path).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn leaderboard(&self, id: u64) -> Result<Leaderboard> {
+        if let Some(json) = self.cached(id) {
+            if let Ok(leaderboard) = Leaderboard::parse(&json) {
+                return Ok(leaderboard);
+            }
+        }
+
+        let json = self.client.leaderboard_json(id)?;
+        let leaderboard = Leaderboard::parse(&json)?;
+
+        let path = self.path(id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, json)?;
+
+        Ok(leaderboard)
+    }
+}
+
+#[cfg(test)]
+const FIXTURE: &str = r#"{
+  "event": "2024",
+  "owner_id": 1,
+  "members": {
+    "1": {
+      "id": 1, "name": "alice", "stars": 3, "local_score": 9, "global_score": 0,
+      "last_star_ts": 1733036400,
+      "completion_day_level": {
+        "1": {
+          "1": {"get_star_ts": 1733029500, "star_index": 10},
+          "2": {"get_star_ts": 1733029800, "star_index": 12}
+        },
+        "2": {
+          "1": {"get_star_ts": 1733119200, "star_index": 40}
+        }
+      }
+    },
+    "2": {
+      "id": 2, "name": null, "stars": 1, "local_score": 2, "global_score": 0,
+      "last_star_ts": 1733030000,
+      "completion_day_level": {
+        "1": {"1": {"get_star_ts": 1733030000, "star_index": 11}}
+      }
+    },
+    "3": {
+      "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+      "last_star_ts": 0, "completion_day_level": {}
+    }
+  }
+}"#;
+
+#[test]
+fn test_parse_leaderboard() {
+    let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+    let ranking: Vec<_> = leaderboard.ranking().iter().map(|m| m.id).collect();
+
+    assert_eq!(vec![1, 2, 3], ranking);
+    assert_eq!(vec![1, 2], leaderboard.days());
+    assert_eq!(Some(1733029800), leaderboard.members["1"].star_ts(1, 2));
+    assert_eq!(None, leaderboard.members["2"].star_ts(1, 2));
+    assert_eq!(
+        "(anonymous user #2)",
+        leaderboard.members["2"].display_name()
+    );
+}
+
+#[test]
+fn test_standings() {
+    let standings = Leaderboard::parse(FIXTURE).unwrap().standings();
+
+    assert_eq!(
+        "  # score stars  name
+  1     9     3  alice
+  2     2     1  (anonymous user #2)
+  3     0     0  carol
+
+day 1
+  alice                   0:05:00     0:10:00  (+0:05:00)
+  (anonymous user #2)     0:13:20
+
+day 2
+  alice                   1:00:00
+",
+        standings
+    );
+}
+
+#[test]
+fn test_standings_align_non_ascii_names() {
+    let json = FIXTURE.replace(r#""name": null"#, r#""name": "Zoë Łukasiewicz""#);
+    let standings = Leaderboard::parse(&json).unwrap().standings();
+
+    assert!(standings.contains(
+        "day 1
+  alice               0:05:00     0:10:00  (+0:05:00)
+  Zoë Łukasiewicz     0:13:20
+"
+    ));
+}
+
+#[test]
+fn test_leaderboard_source_caches() {
+    use crate::mock::{MockResponse, MockServer};
+
+    let server = MockServer::start(|req| {
+        if req.path == "/2024/leaderboard/private/view/1.json"
+            && req.header("cookie") == Some("session=abc")
+        {
+            MockResponse::ok(FIXTURE)
+        } else {
+            MockResponse::new(404, "Not Found")
+        }
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let source = LeaderboardSource::new(
+        AocClient::new(server.url(), 2024).with_session("abc"),
+        InputCache::new(dir.path()),
+    );
+
+    assert_eq!(3, source.leaderboard(1).unwrap().members.len());
+    assert_eq!(3, source.leaderboard(1).unwrap().members.len());
+    assert_eq!(1, server.hits());
+
+    let source = source.with_max_age(Duration::ZERO);
+    source.leaderboard(1).unwrap();
+    assert_eq!(2, server.hits());
+}
+
+#[test]
+fn test_leaderboard_login_page() {
+    let server = crate::mock::MockServer::fixed(200, "<!DOCTYPE html><html></html>");
+    let dir = tempfile::tempdir().unwrap();
+    let source = LeaderboardSource::new(
+        AocClient::new(server.url(), 2024).with_session("abc"),
+        InputCache::new(dir.path()),
+    );
+
+    assert!(matches!(
+        source.leaderboard(1),
+        Err(AocError::ExpiredSession)
+    ));
+    assert!(!source.path(1).exists());
+}
@@ -0,0 +1,293 @@
+use crate::cache::InputCache;
+use crate::submit::Verdict;
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// One judged submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub day: usize,
+    pub part: usize,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Why the ledger refused to send an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved {
+        answer: String,
+    },
+    KnownWrong {
+        verdict: Verdict,
+    },
+    /// Not below an answer that was too high.
+    NotBelow {
+        too_high: i128,
+    },
+    /// Not above an answer that was too low.
+    NotAbove {
+        too_low: i128,
+    },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::AlreadySolved { answer } => {
+                write!(f, "already solved with {}", answer)
+            }
+            Rejection::KnownWrong { verdict } => {
+                write!(f, "already submitted and it was {}", verdict)
+            }
+            Rejection::NotBelow { too_high } => {
+                write!(f, "must be below {}, which was too high", too_high)
+            }
+            Rejection::NotAbove { too_low } => {
+                write!(f, "must be above {}, which was too low", too_low)
+            }
+        }
+    }
+}
+
+/// Persistent record of submissions, used to avoid wasting guesses.
+///
+/// Stored as one tab-separated line per submission: day, part, verdict and answer.
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    pub fn open<P: Into<PathBuf>>(path: P) -> io::Result<Self> {
+        let path = path.into();
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let submissions = text.lines().filter_map(parse_line).collect();
+
+        Ok(Self { path, submissions })
+    }
+
+    /// The ledger kept next to the cached inputs of the year.
+    pub fn for_cache(cache: &InputCache) -> io::Result<Self> {
+        Self::open(cache.year_dir().join("ledger.tsv"))
+    }
+
+    pub fn from_env() -> crate::Result<Self> {
+        Ok(Self::for_cache(&InputCache::from_env()?)?)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    pub fn history(&self, day: usize, part: usize) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Exclusive range a numeric answer must fall in, from too low/too high verdicts.
+    pub fn bounds(&self, day: usize, part: usize) -> (Option<i128>, Option<i128>) {
+        let mut low = None;
+        let mut high = None;
+
+        for s in self.history(day, part) {
+            let Ok(value) = s.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match s.verdict {
+                Verdict::TooLow => low = low.max(Some(value)),
+                Verdict::TooHigh => high = Some(high.map_or(value, |h: i128| h.min(value))),
+                _ => {}
+            }
+        }
+
+        (low, high)
+    }
+
+    /// Returns why `answer` shouldn't be sent, if the ledger already knows.
+    pub fn check(&self, day: usize, part: usize, answer: &str) -> Result<(), Rejection> {
+        for s in self.history(day, part) {
+            match &s.verdict {
+                Verdict::Correct => {
+                    return Err(Rejection::AlreadySolved {
+                        answer: s.answer.clone(),
+                    })
+                }
+                verdict if s.answer == answer => {
+                    return Err(Rejection::KnownWrong {
+                        verdict: verdict.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let (low, high) = self.bounds(day, part);
+
+            if let Some(too_low) = low.filter(|low| value <= *low) {
+                return Err(Rejection::NotAbove { too_low });
+            }
+            if let Some(too_high) = high.filter(|high| value >= *high) {
+                return Err(Rejection::NotBelow { too_high });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Appends a judged submission. Verdicts that say nothing about the answer are skipped.
+    pub fn record(
+        &mut self,
+        day: usize,
+        part: usize,
+        answer: &str,
+        verdict: &Verdict,
+    ) -> io::Result<()> {
+        let Some(token) = verdict_token(verdict) else {
+            return Ok(());
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}\t{}\t{}\t{}", day, part, token, answer)?;
+
+        self.submissions.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        });
+
+        Ok(())
+    }
+}
+
+fn verdict_token(verdict: &Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Correct => Some("correct"),
+        Verdict::TooHigh => Some("too_high"),
+        Verdict::TooLow => Some("too_low"),
+        Verdict::Wrong => Some("wrong"),
+        Verdict::RateLimited(_) | Verdict::AlreadySolved | Verdict::Unknown(_) => None,
+    }
+}
+
+fn parse_line(line: &str) -> Option<Submission> {
+    let mut fields = line.splitn(4, '\t');
+
+    let day = fields.next()?.parse().ok()?;
+    let part = fields.next()?.parse().ok()?;
+    let verdict = match fields.next()? {
+        "correct" => Verdict::Correct,
+        "too_high" => Verdict::TooHigh,
+        "too_low" => Verdict::TooLow,
+        "wrong" => Verdict::Wrong,
+        _ => return None,
+    };
+    let answer = fields.next()?.to_string();
+
+    Some(Submission {
+        day,
+        part,
+        answer,
+        verdict,
+    })
+}
+
+#[test]
+fn test_rejects_known_answers() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut ledger = Ledger::open(dir.path().join("ledger.tsv")).unwrap();
+
+    ledger.record(1, 1, "abc", &Verdict::Wrong).unwrap();
+
+    assert_eq!(
+        Err(Rejection::KnownWrong {
+            verdict: Verdict::Wrong
+        }),
+        ledger.check(1, 1, "abc")
+    );
+    assert_eq!(Ok(()), ledger.check(1, 1, "abd"));
+    assert_eq!(Ok(()), ledger.check(1, 2, "abc"));
+
+    ledger.record(1, 1, "abd", &Verdict::Correct).unwrap();
+
+    assert_eq!(
+        Err(Rejection::AlreadySolved {
+            answer: "abd".into()
+        }),
+        ledger.check(1, 1, "xyz")
+    );
+}
+
+#[test]
+fn test_rejects_out_of_bounds() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut ledger = Ledger::open(dir.path().join("ledger.tsv")).unwrap();
+
+    ledger.record(7, 1, "100", &Verdict::TooLow).unwrap();
+    ledger.record(7, 1, "500", &Verdict::TooHigh).unwrap();
+    ledger.record(7, 1, "300", &Verdict::TooHigh).unwrap();
+    ledger.record(7, 1, "200", &Verdict::Wrong).unwrap();
+
+    assert_eq!((Some(100), Some(300)), ledger.bounds(7, 1));
+    assert_eq!(
+        Err(Rejection::NotAbove { too_low: 100 }),
+        ledger.check(7, 1, "50")
+    );
+    assert_eq!(
+        Err(Rejection::NotBelow { too_high: 300 }),
+        ledger.check(7, 1, "301")
+    );
+    assert_eq!(
+        Err(Rejection::NotBelow { too_high: 300 }),
+        ledger.check(7, 1, "400")
+    );
+    assert_eq!(Ok(()), ledger.check(7, 1, "250"));
+}
+
+#[test]
+fn test_persists_and_skips_unjudged() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("2024").join("ledger.tsv");
+
+    let mut ledger = Ledger::open(&path).unwrap();
+    ledger.record(2, 1, "42", &Verdict::TooLow).unwrap();
+    ledger
+        .record(2, 1, "43", &Verdict::RateLimited(Default::default()))
+        .unwrap();
+
+    let ledger = Ledger::open(&path).unwrap();
+
+    assert_eq!(
+        vec![Submission {
+            day: 2,
+            part: 1,
+            answer: "42".into(),
+            verdict: Verdict::TooLow,
+        }],
+        ledger.submissions()
+    );
+}
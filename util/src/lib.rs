@@ -1,137 +1,157 @@
-use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
-use reqwest::blocking;
+mod cache;
+mod client;
+mod config;
+mod dir;
+mod error;
+mod examples;
+mod grid;
+mod leaderboard;
+mod ledger;
+#[cfg(test)]
+mod mock;
+mod prefetch;
+mod rect;
+mod render;
+mod search;
+mod source;
+mod sparse;
+mod submit;
+mod throttle;
+mod torus;
+mod unlock;
+mod vault;
+mod vec2d;
+mod vec3d;
+mod vecn;
+
 use std::str::FromStr;
-use std::ops::{Add, Div, Rem, Sub};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Vec2d {
-    pub x: isize,
-    pub y: isize,
+pub use cache::{year_from_env, year_from_env_var, InputCache, YEAR};
+pub use client::{AocClient, BASE_URL, RETRIES, RETRY_DELAY};
+pub use config::{active_profile, without_profile_arg, Config, Profile};
+pub use dir::{Dir4, Dir8};
+pub use error::{unwrap_or_exit, AocError, Result};
+pub use examples::{download_examples, example, extract_examples, save_examples, ExampleStore};
+pub use grid::{invalid_cell, Grid, GridView, IntoIter, Markers};
+pub use leaderboard::{Leaderboard, LeaderboardSource, Member, Star, POLL_INTERVAL};
+pub use ledger::{Ledger, Rejection, Submission};
+pub use prefetch::{days_in_year, prefetch, prefetch_inputs, PrefetchReport};
+pub use rect::Rect;
+pub use render::Render;
+pub use search::{dijkstra, Route};
+pub use source::{
+    default_source, refreshing_source, source_from_args, CachedSource, FileSource, HttpSource,
+    InputSource, MemorySource, StdinSource,
+};
+pub use sparse::SparseGrid;
+pub use submit::{report_answer, submit_answer, Verdict};
+pub use throttle::{Throttle, MIN_INTERVAL};
+pub use torus::Torus;
+pub use unlock::{time_until_unlock, unlock_time, Clock, SystemClock, UnlockingSource, MAX_WAIT};
+pub use vault::{Vault, VaultKey, VaultSource};
+pub use vec2d::Vec2d;
+pub use vec3d::Vec3d;
+pub use vecn::VecN;
+
+/// Returns the input of the day from the source picked by [`source_from_args`].
+pub fn try_fetch_input(day: usize) -> Result<String> {
+    source_from_args(std::env::args().skip(1))?.input(day)
 }
 
-impl Vec2d {
-    pub fn new(x: isize, y: isize) -> Self {
-        Self { x, y }
-    }
-
-    pub fn from_tuple(t: (isize, isize)) -> Self {
-        Self::new(t.0, t.1)
-    }
+/// Like [`try_fetch_input`], but prints the error and exits on failure.
+pub fn fetch_input(day: usize) -> String {
+    unwrap_or_exit(try_fetch_input(day))
 }
 
-impl From<(isize, isize)> for Vec2d {
-    fn from(t: (isize, isize)) -> Self {
-        Self::new(t.0, t.1)
-    }
+/// Downloads the input of the day again and replaces the cached copy.
+pub fn refresh_input(day: usize) -> Result<String> {
+    refreshing_source()?.input(day)
 }
 
-impl Add for Vec2d {
-    type Output = Vec2d;
-
-    fn add(self, other: Vec2d) -> Vec2d {
-        Vec2d {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
+/// Removes every cached input.
+pub fn clear_cache() -> Result<()> {
+    Ok(InputCache::from_env()?.clear()?)
 }
 
-impl Sub for Vec2d {
-    type Output = Vec2d;
-
-    fn sub(self, other: Vec2d) -> Vec2d {
-        Vec2d {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
+pub fn download_input(day: usize) -> Result<String> {
+    HttpSource::from_env()?.input(day)
 }
 
-impl Rem for Vec2d {
-    type Output = Vec2d;
+/// Splits whitespace-separated tokens into columns, failing on the first bad token.
+pub fn try_parse_as_columns<T: FromStr>(input: &str) -> Result<Vec<Vec<T>>> {
+    let mut columns: Vec<Vec<T>> = Vec::new();
 
-    fn rem(self, other: Vec2d) -> Vec2d {
-        Vec2d {
-            x: self.x % other.x,
-            y: self.y % other.y,
+    for row in try_parse_as_rows(input)? {
+        // Ensure the columns vector has enough capacity
+        while columns.len() < row.len() {
+            columns.push(Vec::new());
         }
-    }
-}
 
-impl Div<isize> for Vec2d {
-    type Output = Vec2d;
-
-    fn div(self, other: isize) -> Vec2d {
-        Vec2d {
-            x: self.x / other,
-            y: self.y / other,
+        for (i, value) in row.into_iter().enumerate() {
+            columns[i].push(value);
         }
     }
+
+    Ok(columns)
 }
 
-pub fn fetch_input(day: usize) -> String {
-    let url = format!("https://adventofcode.com/2024/day/{}/input", day);
-
-    let session_token =
-        std::env::var("AOC_SESSION").expect("missing AOC_SESSION environment variable");
-
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        COOKIE,
-        HeaderValue::from_str(&format!("session={}", session_token)).unwrap(),
-    );
-
-    let client = blocking::Client::new();
-    let response = client
-        .get(url)
-        .headers(headers)
-        .send()
-        .expect("coudln't request input");
-
-    if response.status().is_success() {
-        response.text().expect("couldn't fetch input as text")
-    } else {
-        panic!("request failed with status: {}", response.status());
-    }
+/// Splits whitespace-separated tokens into rows, failing on the first bad token.
+pub fn try_parse_as_rows<T: FromStr>(input: &str) -> Result<Vec<Vec<T>>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.split_whitespace()
+                .map(|token| {
+                    token.parse().map_err(|_| {
+                        let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+
+                        AocError::Parse {
+                            line: y + 1,
+                            column: line[..offset].chars().count() + 1,
+                            token: token.to_string(),
+                        }
+                    })
+                })
+                .collect()
+        })
+        .collect()
 }
 
+/// Like [`try_parse_as_columns`], but prints the error and exits on failure.
 pub fn parse_as_columns<T: FromStr>(input: &str) -> Vec<Vec<T>> {
-    let mut columns: Vec<Vec<T>> = Vec::new();
+    unwrap_or_exit(try_parse_as_columns(input))
+}
 
-    // Process each line
-    for line in input.lines() {
-        // Split the line into tokens
-        let tokens: Vec<&str> = line.split_whitespace().collect();
+/// Like [`try_parse_as_rows`], but prints the error and exits on failure.
+pub fn parse_as_rows<T: FromStr>(input: &str) -> Vec<Vec<T>> {
+    unwrap_or_exit(try_parse_as_rows(input))
+}
 
-        // Ensure the columns vector has enough capacity
-        while columns.len() < tokens.len() {
-            columns.push(Vec::new());
-        }
+#[test]
+fn test_parse_as_columns() {
+    let columns: Vec<Vec<usize>> = try_parse_as_columns("3   4\n4   3\n2   5\n").unwrap();
 
-        // Parse and store each token in the appropriate column
-        for (i, token) in tokens.iter().enumerate() {
-            if let Ok(value) = token.parse() {
-                columns[i].push(value);
-            }
-        }
-    }
+    assert_eq!(vec![vec![3, 4, 2], vec![4, 3, 5]], columns);
+}
+
+#[test]
+fn test_parse_as_rows() {
+    let rows: Vec<Vec<usize>> = try_parse_as_rows("7 6 4\n1 2\n").unwrap();
 
-    columns
+    assert_eq!(vec![vec![7, 6, 4], vec![1, 2]], rows);
 }
 
-pub fn parse_as_rows<T: FromStr>(input: &str) -> Vec<Vec<T>> {
-    // Initialize a vector to store rows
-    let mut rows: Vec<Vec<T>> = Vec::new();
-
-    // Process each line
-    for line in input.lines() {
-        // Split the line into tokens and parse them into integers
-        let row: Vec<T> = line
-            .split_whitespace()
-            .filter_map(|token| token.parse().ok())
-            .collect();
-        rows.push(row);
+#[test]
+fn test_parse_error_position() {
+    let result: Result<Vec<Vec<usize>>> = try_parse_as_rows("1 2 3\n4  x5 6\n");
+
+    match result {
+        Err(AocError::Parse {
+            line,
+            column,
+            token,
+        }) => assert_eq!((2, 4, "x5"), (line, column, token.as_str())),
+        other => panic!("unexpected {:?}", other),
     }
-
-    rows
 }
@@ -0,0 +1,172 @@
+use aoc24_util::{
+    active_profile, days_in_year, download_examples, prefetch_inputs, save_examples, submit_answer,
+    unwrap_or_exit, without_profile_arg, year_from_env, Config, InputCache, LeaderboardSource,
+    Ledger, Vault,
+};
+
+const USAGE: &str = "usage:
+  aoc24-util submit <day> <part> <answer>
+  aoc24-util ledger [day]
+  aoc24-util examples <day> [page.html]
+  aoc24-util leaderboard [id]
+  aoc24-util prefetch [year]
+  aoc24-util profiles
+  aoc24-util vault seal [day...]
+
+Pick a profile from the config file with --profile <name> or AOC_PROFILE.";
+
+/// Prints what is wrong with the arguments and the usage, then exits.
+fn usage_error(message: &str) -> ! {
+    eprintln!("error: {}\n{}", message, USAGE);
+    std::process::exit(1);
+}
+
+fn parse_number(arg: Option<&String>, name: &str) -> usize {
+    arg.and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| usage_error(&format!("missing or invalid {}", name)))
+}
+
+fn print_ledger(day: Option<usize>) {
+    let ledger = unwrap_or_exit(Ledger::from_env());
+
+    for s in ledger.submissions() {
+        if day.is_none_or(|day| day == s.day) {
+            println!(
+                "day {} part {}: {} ({})",
+                s.day, s.part, s.answer, s.verdict
+            );
+        }
+    }
+
+    let mut parts: Vec<_> = ledger
+        .submissions()
+        .iter()
+        .map(|s| (s.day, s.part))
+        .filter(|(d, _)| day.is_none_or(|day| day == *d))
+        .collect();
+    parts.sort();
+    parts.dedup();
+
+    for (day, part) in parts {
+        match ledger.bounds(day, part) {
+            (None, None) => {}
+            (low, high) => println!(
+                "day {} part {}: answer is in ({}, {})",
+                day,
+                part,
+                low.map_or("-inf".into(), |v| v.to_string()),
+                high.map_or("inf".into(), |v| v.to_string()),
+            ),
+        }
+    }
+}
+
+fn print_profiles() {
+    let config = unwrap_or_exit(Config::from_env());
+    let active = unwrap_or_exit(active_profile()).map(|p| p.name.as_str());
+
+    if let Some(path) = Config::path() {
+        println!("config: {}", path.display());
+    }
+
+    for (name, profile) in &config.profiles {
+        println!(
+            "{} {} (year {}, session {}, cache {})",
+            if Some(name.as_str()) == active {
+                "*"
+            } else {
+                " "
+            },
+            name,
+            profile.year.map_or("-".into(), |y| y.to_string()),
+            if profile.session.is_some() {
+                "set"
+            } else {
+                "from AOC_SESSION"
+            },
+            profile
+                .cache_dir
+                .as_ref()
+                .map_or("default".into(), |d| d.display().to_string()),
+        );
+    }
+}
+
+fn seal_vault(days: &[String]) {
+    let Some(vault) = unwrap_or_exit(Vault::from_env()) else {
+        eprintln!("error: set AOC_VAULT_KEY or AOC_VAULT_KEY_FILE to seal inputs");
+        std::process::exit(1);
+    };
+    let cache = unwrap_or_exit(InputCache::from_env());
+
+    let days: Vec<usize> = if days.is_empty() {
+        (1..=days_in_year(cache.year()))
+            .filter(|day| cache.path(*day).exists())
+            .collect()
+    } else {
+        days.iter().map(|d| parse_number(Some(d), "day")).collect()
+    };
+
+    for day in days {
+        let Some(input) = cache.load(day) else {
+            eprintln!("day {}: not cached, skipped", day);
+            continue;
+        };
+
+        unwrap_or_exit(vault.seal(day, &input));
+        println!("day {}: sealed into {}", day, vault.path(day).display());
+    }
+}
+
+fn main() {
+    let args = without_profile_arg(std::env::args().skip(1));
+
+    match args.first().map(|s| s.as_str()) {
+        Some("submit") => {
+            let day = parse_number(args.get(1), "day");
+            let part = parse_number(args.get(2), "part");
+            let answer = args.get(3).unwrap_or_else(|| usage_error("missing answer"));
+
+            println!("{}", unwrap_or_exit(submit_answer(day, part, answer)));
+        }
+        Some("examples") => {
+            let day = parse_number(args.get(1), "day");
+            let count = match args.get(2) {
+                Some(path) => {
+                    let html = unwrap_or_exit(std::fs::read_to_string(path).map_err(Into::into));
+                    unwrap_or_exit(save_examples(day, &html))
+                }
+                None => unwrap_or_exit(download_examples(day)),
+            };
+
+            println!("saved {} examples for day {}", count, day);
+        }
+        Some("leaderboard") => {
+            let id = args
+                .get(1)
+                .cloned()
+                .or_else(|| std::env::var("AOC_LEADERBOARD").ok());
+            let id = parse_number(id.as_ref(), "leaderboard id") as u64;
+            let leaderboard =
+                unwrap_or_exit(LeaderboardSource::from_env().and_then(|s| s.leaderboard(id)));
+
+            print!("{}", leaderboard.standings());
+        }
+        Some("prefetch") => {
+            let year = match args.get(1) {
+                Some(_) => parse_number(args.get(1), "year"),
+                None => unwrap_or_exit(year_from_env()),
+            };
+            let report = unwrap_or_exit(prefetch_inputs(year));
+
+            println!("{}", report);
+            if !report.is_success() {
+                std::process::exit(1);
+            }
+        }
+        Some("vault") if args.get(1).is_some_and(|a| a == "seal") => seal_vault(&args[2..]),
+        Some("profiles") => print_profiles(),
+        Some("ledger") => print_ledger(args.get(1).map(|_| parse_number(args.get(1), "day"))),
+        _ => println!("{}", USAGE),
+    }
+}
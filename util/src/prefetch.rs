@@ -0,0 +1,138 @@
+use crate::cache::InputCache;
+use crate::client::AocClient;
+use crate::error::{AocError, Result};
+use crate::source::{check_body, HttpSource, InputSource};
+use crate::unlock::{time_until_unlock, Clock, SystemClock};
+use std::fmt::{self, Display};
+
+/// Number of puzzles in the event; from 2025 on there are only twelve.
+pub fn days_in_year(year: usize) -> usize {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// What [`prefetch`] did for each unlocked day.
+#[derive(Debug, Default)]
+pub struct PrefetchReport {
+    pub fetched: Vec<usize>,
+    /// Days that were already cached.
+    pub skipped: Vec<usize>,
+    pub failed: Vec<(usize, AocError)>,
+}
+
+impl PrefetchReport {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl Display for PrefetchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = |days: &[usize]| {
+            days.iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        writeln!(f, "fetched: {}", days(&self.fetched))?;
+        writeln!(f, "skipped: {}", days(&self.skipped))?;
+        write!(f, "failed: ")?;
+        for (day, e) in &self.failed {
+            write!(f, "\n  day {}: {}", day, e)?;
+        }
+        Ok(())
+    }
+}
+
+/// Downloads every unlocked day of the cache's year that isn't cached yet.
+///
+/// Days are fetched one after the other, so the source's throttling applies.
+pub fn prefetch<S, C>(source: &S, cache: &InputCache, clock: &C) -> PrefetchReport
+where
+    S: InputSource,
+    C: Clock,
+{
+    let mut report = PrefetchReport::default();
+
+    for day in 1..=days_in_year(cache.year()) {
+        if time_until_unlock(clock, cache.year(), day).is_some() {
+            break;
+        }
+
+        let cached = cache.load(day);
+        if cached.is_some_and(|input| check_body(day, 200, &input).is_ok()) {
+            report.skipped.push(day);
+            continue;
+        }
+
+        let result = source
+            .input(day)
+            .and_then(|input| Ok(cache.store(day, &input)?));
+        match result {
+            Ok(()) => report.fetched.push(day),
+            Err(e) => report.failed.push((day, e)),
+        }
+    }
+
+    report
+}
+
+/// Fills the input cache with every unlocked day of `year`, using the environment's settings.
+pub fn prefetch_inputs(year: usize) -> Result<PrefetchReport> {
+    let source = HttpSource::with_client(AocClient::from_env()?.with_year(year));
+    let cache = InputCache::from_env()?.with_year(year);
+
+    Ok(prefetch(&source, &cache, &SystemClock))
+}
+
+#[test]
+fn test_prefetch() {
+    use crate::mock::{FakeClock, MockResponse, MockServer};
+    use crate::unlock::unlock_time;
+    use std::time::Duration;
+
+    let server = MockServer::start(|req| match req.path.as_str() {
+        "/2024/day/1/input" => MockResponse::ok("one\n"),
+        "/2024/day/3/input" => MockResponse::new(500, "Internal Server Error"),
+        "/2024/day/4/input" => MockResponse::ok("four\n"),
+        _ => MockResponse::new(404, "Not Found"),
+    });
+    let source = HttpSource::with_client(
+        AocClient::new(server.url(), 2024)
+            .with_session("abc")
+            .with_retries(0, Duration::ZERO),
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path()).with_year(2024);
+    cache.store(2, "two\n").unwrap();
+
+    let clock = FakeClock::at(unlock_time(2024, 4) + Duration::from_secs(60));
+    let report = prefetch(&source, &cache, &clock);
+
+    assert_eq!(vec![1, 4], report.fetched);
+    assert_eq!(vec![2], report.skipped);
+    assert_eq!(
+        vec![3],
+        report.failed.iter().map(|f| f.0).collect::<Vec<_>>()
+    );
+    assert_eq!(3, server.hits());
+    assert_eq!(Some("four\n".to_string()), cache.load(4));
+    assert_eq!(None, cache.load(3));
+
+    let report = prefetch(&source, &cache, &clock);
+    assert_eq!(vec![1, 2, 4], report.skipped);
+    assert!(report
+        .to_string()
+        .starts_with("fetched: \nskipped: 1, 2, 4\nfailed: \n  day 3: request to "));
+}
+
+#[test]
+fn test_days_in_year() {
+    assert_eq!(25, days_in_year(2024));
+    assert_eq!(12, days_in_year(2025));
+}
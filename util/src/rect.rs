@@ -0,0 +1,157 @@
+use crate::Vec2d;
+
+/// An axis-aligned rectangle of grid positions, from `min` up to but excluding `max`.
+///
+/// A rectangle whose `max` isn't beyond `min` on both axes is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Vec2d,
+    pub max: Vec2d,
+}
+
+impl Rect {
+    pub const EMPTY: Rect = Rect::new(Vec2d::ZERO, Vec2d::ZERO);
+
+    pub const fn new(min: Vec2d, max: Vec2d) -> Self {
+        Self { min, max }
+    }
+
+    /// The rectangle with its top left corner at `min`, `size.x` wide and `size.y` high.
+    pub fn from_size(min: Vec2d, size: Vec2d) -> Self {
+        Self::new(min, min + size)
+    }
+
+    /// The smallest rectangle holding every point; empty without points.
+    ///
+    /// Like any `Rect`, it excludes `max`, which is one past the largest point on
+    /// both axes. [`Vec3d::bounding_box`](crate::Vec3d::bounding_box) returns the
+    /// corners themselves instead.
+    pub fn covering<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = Vec2d>,
+    {
+        points.into_iter().fold(Self::EMPTY, |rect, p| {
+            rect.union(Self::new(p, p + Vec2d::new(1, 1)))
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x).max(0) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y).max(0) as usize
+    }
+
+    pub fn size(&self) -> Vec2d {
+        Vec2d::new(self.width() as isize, self.height() as isize)
+    }
+
+    /// Number of positions inside.
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    pub fn contains(&self, pos: Vec2d) -> bool {
+        (self.min.x..self.max.x).contains(&pos.x) && (self.min.y..self.max.y).contains(&pos.y)
+    }
+
+    /// The positions inside both rectangles, or `None` if they don't overlap.
+    pub fn intersect(&self, other: Rect) -> Option<Rect> {
+        let rect = Rect::new(self.min.max(other.min), self.max.min(other.max));
+
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// The smallest rectangle holding both; an empty one is ignored.
+    pub fn union(&self, other: Rect) -> Rect {
+        if self.is_empty() {
+            other
+        } else if other.is_empty() {
+            *self
+        } else {
+            Rect::new(self.min.min(other.min), self.max.max(other.max))
+        }
+    }
+
+    /// Moves each side outwards by `by`, or inwards if it is negative.
+    pub fn grow(&self, by: isize) -> Rect {
+        let by = Vec2d::new(by, by);
+
+        Rect::new(self.min - by, self.max + by)
+    }
+
+    /// Every position inside, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2d> {
+        let Rect { min, max } = *self;
+
+        (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| Vec2d::new(x, y)))
+    }
+}
+
+#[test]
+fn test_rect() {
+    let rect = Rect::from_size(Vec2d::new(1, 2), Vec2d::new(3, 2));
+
+    assert_eq!(Vec2d::new(4, 4), rect.max);
+    assert_eq!((3, 2, 6), (rect.width(), rect.height(), rect.area()));
+    assert!(rect.contains(Vec2d::new(1, 2)));
+    assert!(rect.contains(Vec2d::new(3, 3)));
+    assert!(!rect.contains(Vec2d::new(4, 3)));
+    assert!(!rect.contains(Vec2d::new(0, 2)));
+
+    assert!(Rect::EMPTY.is_empty());
+    assert!(Rect::new(Vec2d::new(3, 0), Vec2d::new(1, 5)).is_empty());
+    assert_eq!(0, Rect::new(Vec2d::new(3, 0), Vec2d::new(1, 5)).area());
+}
+
+#[test]
+fn test_intersect_and_union() {
+    let a = Rect::new(Vec2d::new(0, 0), Vec2d::new(4, 4));
+    let b = Rect::new(Vec2d::new(2, 3), Vec2d::new(6, 5));
+
+    assert_eq!(
+        Some(Rect::new(Vec2d::new(2, 3), Vec2d::new(4, 4))),
+        a.intersect(b)
+    );
+    assert_eq!(
+        None,
+        a.intersect(Rect::from_size(Vec2d::new(4, 0), Vec2d::new(2, 2)))
+    );
+    assert_eq!(Rect::new(Vec2d::new(0, 0), Vec2d::new(6, 5)), a.union(b));
+    assert_eq!(a, a.union(Rect::EMPTY));
+    assert_eq!(b, Rect::EMPTY.union(b));
+}
+
+#[test]
+fn test_grow() {
+    let rect = Rect::new(Vec2d::new(0, 0), Vec2d::new(4, 3));
+
+    assert_eq!(
+        Rect::new(Vec2d::new(-1, -1), Vec2d::new(5, 4)),
+        rect.grow(1)
+    );
+    assert_eq!(2, rect.grow(-1).area());
+    assert!(rect.grow(-2).is_empty());
+}
+
+#[test]
+fn test_positions_and_covering() {
+    let rect = Rect::new(Vec2d::new(-1, 0), Vec2d::new(1, 2));
+
+    assert_eq!(
+        vec![(-1, 0), (0, 0), (-1, 1), (0, 1)],
+        rect.positions().map(|p| (p.x, p.y)).collect::<Vec<_>>()
+    );
+    assert_eq!(0, Rect::EMPTY.positions().count());
+
+    let points = [Vec2d::new(3, -1), Vec2d::new(-2, 4), Vec2d::new(0, 0)];
+    let bounds = Rect::covering(points);
+    assert_eq!(Rect::new(Vec2d::new(-2, -1), Vec2d::new(4, 5)), bounds);
+    assert!(points.iter().all(|p| bounds.contains(*p)));
+    assert_eq!(Rect::EMPTY, Rect::covering([]));
+}
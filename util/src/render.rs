@@ -0,0 +1,223 @@
+use crate::{Grid, Vec2d};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+/// Text drawing of a [`Grid`], one character per cell, built with [`Grid::render`].
+///
+/// Overlays are drawn on top of the cells, the last one added winning. With
+/// [`Render::with_diff`], the grid is drawn next to an earlier state of it and a
+/// panel marking every cell that changed since, with `*`.
+///
+/// ```text
+///   0123
+/// 0 #..#
+/// 1 .@O.
+/// O: path
+/// ```
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    format: Box<dyn Fn(&T) -> char + 'a>,
+    overlay: HashMap<Vec2d, char>,
+    legend: Vec<(char, String)>,
+    rulers: bool,
+    before: Option<Grid<(char, bool)>>,
+}
+
+impl<T> Grid<T> {
+    /// Draws the grid, turning each cell into a character with `format`.
+    pub fn render<'a, F>(&'a self, format: F) -> Render<'a, T>
+    where
+        F: Fn(&T) -> char + 'a,
+    {
+        Render {
+            grid: self,
+            format: Box::new(format),
+            overlay: HashMap::new(),
+            legend: vec![],
+            rulers: false,
+            before: None,
+        }
+    }
+}
+
+impl<'a, T> Render<'a, T> {
+    /// Draws `ch` at each of `points` inside the grid, such as a path or a visited set.
+    pub fn with_overlay<I>(mut self, points: I, ch: char) -> Self
+    where
+        I: IntoIterator<Item = Vec2d>,
+    {
+        let grid = self.grid;
+        self.overlay.extend(
+            points
+                .into_iter()
+                .filter(|p| grid.contains(*p))
+                .map(|p| (p, ch)),
+        );
+        self
+    }
+
+    /// Draws `ch` at a single point, such as a robot or a goal.
+    pub fn with_point(self, pos: Vec2d, ch: char) -> Self {
+        self.with_overlay([pos], ch)
+    }
+
+    /// Explains what `ch` stands for, on a line below the grid.
+    pub fn with_legend<S: Into<String>>(mut self, ch: char, meaning: S) -> Self {
+        self.legend.push((ch, meaning.into()));
+        self
+    }
+
+    /// Numbers the columns above the grid and the rows on its left.
+    pub fn with_rulers(mut self) -> Self {
+        self.rulers = true;
+        self
+    }
+
+    /// Draws `before`, then the grid, then which cells differ between them.
+    ///
+    /// Panics unless both grids have the same size.
+    pub fn with_diff(mut self, before: &Grid<T>) -> Self
+    where
+        T: PartialEq,
+    {
+        let grid = self.grid;
+        assert_eq!(
+            (grid.width(), grid.height()),
+            (before.width(), before.height()),
+            "can't diff grids of different sizes"
+        );
+
+        let cells = before
+            .iter()
+            .map(|(pos, cell)| ((self.format)(cell), *cell != grid[pos]))
+            .collect();
+        self.before = Some(Grid::from_vec(grid.width(), grid.height(), cells));
+        self
+    }
+
+    fn cell(&self, pos: Vec2d) -> char {
+        match self.overlay.get(&pos) {
+            Some(ch) => *ch,
+            None => (self.format)(&self.grid[pos]),
+        }
+    }
+}
+
+impl<T> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, height) = (self.grid.width(), self.grid.height());
+        let panels = if self.before.is_some() { 3 } else { 1 };
+        let label_width = digits(height.saturating_sub(1));
+
+        if self.rulers {
+            for place in (0..digits(width.saturating_sub(1))).rev() {
+                let scale = 10usize.pow(place as u32);
+                let ruler: String = (0..width)
+                    .map(|x| match x / scale {
+                        0 if place > 0 => ' ',
+                        n => char::from_digit((n % 10) as u32, 10).unwrap(),
+                    })
+                    .collect();
+
+                let line = vec![ruler; panels].join("   ");
+                writeln!(f, "{:w$} {}", "", line.trim_end(), w = label_width)?;
+            }
+        }
+
+        for y in 0..height {
+            if self.rulers {
+                write!(f, "{:>w$} ", y, w = label_width)?;
+            }
+
+            let row = (0..width).map(|x| Vec2d::new(x as isize, y as isize));
+            if let Some(before) = &self.before {
+                let cells: String = row.clone().map(|p| before[p].0).collect();
+                write!(f, "{} | ", cells)?;
+            }
+
+            let cells: String = row.clone().map(|p| self.cell(p)).collect();
+            write!(f, "{}", cells)?;
+
+            if let Some(before) = &self.before {
+                let marks: String = row.map(|p| if before[p].1 { '*' } else { '.' }).collect();
+                write!(f, " | {}", marks)?;
+            }
+            writeln!(f)?;
+        }
+
+        for (ch, meaning) in &self.legend {
+            writeln!(f, "{}: {}", ch, meaning)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of decimal digits of `n`.
+fn digits(n: usize) -> usize {
+    n.checked_ilog10().unwrap_or(0) as usize + 1
+}
+
+#[cfg(test)]
+fn walls() -> Grid<bool> {
+    Grid::parse("#..#\n....\n#..#\n", |ch, _| Ok(ch == '#')).unwrap()
+}
+
+#[cfg(test)]
+fn wall(wall: &bool) -> char {
+    if *wall {
+        '#'
+    } else {
+        '.'
+    }
+}
+
+#[test]
+fn test_render() {
+    let grid = walls();
+
+    assert_eq!("#..#\n....\n#..#\n", grid.render(wall).to_string());
+    assert_eq!(
+        "#..#\n.OO@\n#..#\nO: path\n",
+        grid.render(wall)
+            .with_overlay((0..4).map(|x| Vec2d::new(x, 1)), 'O')
+            .with_overlay([Vec2d::new(0, 1), Vec2d::new(9, 9)], '.')
+            .with_point(Vec2d::new(3, 1), '@')
+            .with_legend('O', "path")
+            .to_string()
+    );
+}
+
+#[test]
+fn test_rulers() {
+    let grid = Grid::new(12, 11, 0);
+
+    let text = grid.render(|_| '.').with_rulers().to_string();
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!("             11", lines[0]);
+    assert_eq!("   012345678901", lines[1]);
+    assert_eq!(" 0 ............", lines[2]);
+    assert_eq!("10 ............", lines[12]);
+}
+
+#[test]
+fn test_diff() {
+    let before = walls();
+    let mut after = before.clone();
+    after[Vec2d::new(0, 0)] = false;
+    after[Vec2d::new(2, 1)] = true;
+
+    assert_eq!(
+        concat!(
+            "  0123   0123   0123\n",
+            "0 #..# | ...# | *...\n",
+            "1 .... | ..#. | ..*.\n",
+            "2 #..# | #..# | ....\n",
+        ),
+        after
+            .render(wall)
+            .with_diff(&before)
+            .with_rulers()
+            .to_string()
+    );
+}
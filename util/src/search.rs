@@ -0,0 +1,111 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// The cheapest way to a goal found by [`dijkstra`].
+#[derive(Debug, Clone)]
+pub struct Route<S> {
+    /// Total cost from the start to the goal.
+    pub cost: usize,
+    /// Every state along the way, from the start to the goal included.
+    pub path: Vec<S>,
+    /// The cost of every state settled before the goal was reached.
+    pub dist: HashMap<S, usize>,
+}
+
+/// Finds the cheapest path from `start` to a state for which `is_goal` is true.
+///
+/// `successors` lists the states reachable in one step from a state, each with
+/// the cost of that step. Returns `None` if no goal can be reached.
+pub fn dijkstra<S, F, I, G>(start: S, mut successors: F, mut is_goal: G) -> Option<Route<S>>
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, usize)>,
+    G: FnMut(&S) -> bool,
+{
+    // The heap only holds indices into `nodes`, so states don't have to be `Ord`.
+    let mut nodes: Vec<(S, Option<usize>)> = vec![(start.clone(), None)];
+    let mut best = HashMap::from([(start, 0)]);
+    let mut dist = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((0, 0))]);
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let state = nodes[index].0.clone();
+        if dist.contains_key(&state) {
+            continue;
+        }
+        dist.insert(state.clone(), cost);
+
+        if is_goal(&state) {
+            let mut path = vec![];
+            let mut next = Some(index);
+            while let Some(index) = next {
+                path.push(nodes[index].0.clone());
+                next = nodes[index].1;
+            }
+            path.reverse();
+
+            return Some(Route { cost, path, dist });
+        }
+
+        for (succ, step) in successors(&state) {
+            let succ_cost = cost + step;
+            if dist.contains_key(&succ) || best.get(&succ).is_some_and(|c| *c <= succ_cost) {
+                continue;
+            }
+
+            best.insert(succ.clone(), succ_cost);
+            nodes.push((succ, Some(index)));
+            heap.push(Reverse((succ_cost, nodes.len() - 1)));
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_dijkstra() {
+    // a -1-> b -1-> c -1-> d, with a shortcut a -5-> d and a dead end b -1-> e.
+    let edges = HashMap::from([
+        ('a', vec![('b', 1), ('d', 5)]),
+        ('b', vec![('c', 1), ('e', 1)]),
+        ('c', vec![('d', 1)]),
+    ]);
+    let successors = |s: &char| edges.get(s).cloned().unwrap_or_default();
+
+    let route = dijkstra('a', successors, |s| *s == 'd').unwrap();
+    assert_eq!(3, route.cost);
+    assert_eq!(vec!['a', 'b', 'c', 'd'], route.path);
+    assert_eq!(Some(&2), route.dist.get(&'e'));
+    assert_eq!(Some(&3), route.dist.get(&'d'));
+
+    let route = dijkstra('b', successors, |s| *s == 'b').unwrap();
+    assert_eq!((0, vec!['b']), (route.cost, route.path));
+
+    assert!(dijkstra('c', successors, |s| *s == 'a').is_none());
+}
+
+#[test]
+fn test_dijkstra_grid() {
+    use crate::{Grid, Vec2d};
+
+    let grid = Grid::parse_chars("..#.\n.##.\n....\n").unwrap();
+    let goal = Vec2d::new(3, 0);
+
+    let route = dijkstra(
+        Vec2d::ZERO,
+        |pos: &Vec2d| {
+            pos.neighbors4()
+                .filter(|p| grid.get(*p) == Some(&'.'))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        },
+        |pos| *pos == goal,
+    )
+    .unwrap();
+
+    assert_eq!(7, route.cost);
+    assert_eq!(8, route.path.len());
+    assert!(route.path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+}
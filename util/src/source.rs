@@ -0,0 +1,467 @@
+use crate::cache::InputCache;
+use crate::client::{status_error, AocClient};
+use crate::error::{AocError, Result};
+use crate::unlock::UnlockingSource;
+use crate::vault::{Vault, VaultSource};
+use std::collections::HashMap;
+use std::io::{self, ErrorKind, Read};
+use std::path::PathBuf;
+
+/// Somewhere a day's puzzle input can be read from.
+pub trait InputSource {
+    fn input(&self, day: usize) -> Result<String>;
+}
+
+impl<S: InputSource + ?Sized> InputSource for Box<S> {
+    fn input(&self, day: usize) -> Result<String> {
+        (**self).input(day)
+    }
+}
+
+/// Downloads inputs through an [`AocClient`].
+#[derive(Debug, Clone, Default)]
+pub struct HttpSource {
+    client: AocClient,
+}
+
+impl HttpSource {
+    /// `base_url` is the scheme and host, e.g. `http://127.0.0.1:8080`.
+    pub fn new<S: Into<String>>(base_url: S, year: usize) -> Self {
+        Self::with_client(AocClient::new(base_url, year))
+    }
+
+    pub fn with_client(client: AocClient) -> Self {
+        Self { client }
+    }
+
+    /// Uses `AOC_BASE_URL` and `AOC_YEAR` if set.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::with_client(AocClient::from_env()?))
+    }
+
+    /// Sends `token` instead of reading `AOC_SESSION` on every request.
+    pub fn with_session<S: Into<String>>(mut self, token: S) -> Self {
+        self.client = self.client.with_session(token);
+        self
+    }
+
+    pub fn client(&self) -> &AocClient {
+        &self.client
+    }
+}
+
+impl InputSource for HttpSource {
+    fn input(&self, day: usize) -> Result<String> {
+        let response = self.client.get_unchecked(&format!("day/{}/input", day))?;
+        let status = response.status();
+        let failure = (!status.is_success()).then(|| status_error(&response));
+        let body = response.text()?;
+
+        check_body(day, status.as_u16(), &body)?;
+
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(body),
+        }
+    }
+}
+
+/// Fails if `body` is one of the pages the site sends instead of the input.
+///
+/// Other failed statuses are left for the caller to report.
+pub(crate) fn check_body(day: usize, status: u16, body: &str) -> Result<()> {
+    if body.contains("Please don't repeatedly request this endpoint") {
+        Err(AocError::RepeatedRequest)
+    } else if status == 404 {
+        Err(AocError::NotUnlocked { day })
+    } else if body.contains("Please log in") {
+        Err(AocError::ExpiredSession)
+    } else if (200..300).contains(&status) && is_html(body) {
+        Err(AocError::HtmlPage)
+    } else {
+        Ok(())
+    }
+}
+
+fn is_html(body: &str) -> bool {
+    let body = body.trim_start().to_ascii_lowercase();
+
+    body.starts_with("<!doctype") || body.starts_with("<html")
+}
+
+/// Reads inputs from a file, or from `day<N>.txt` if the path is a directory.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl InputSource for FileSource {
+    fn input(&self, day: usize) -> Result<String> {
+        let path = if self.path.is_dir() {
+            self.path.join(format!("day{}.txt", day))
+        } else {
+            self.path.clone()
+        };
+
+        std::fs::read_to_string(&path).map_err(|e| {
+            AocError::Io(io::Error::new(
+                e.kind(),
+                format!("couldn't read input from {}: {}", path.display(), e),
+            ))
+        })
+    }
+}
+
+/// Reads the whole standard input, whatever the day is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinSource;
+
+impl InputSource for StdinSource {
+    fn input(&self, _day: usize) -> Result<String> {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    }
+}
+
+/// Serves inputs held in memory, mostly for tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    inputs: HashMap<usize, String>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_input<S: Into<String>>(mut self, day: usize, input: S) -> Self {
+        self.inputs.insert(day, input.into());
+        self
+    }
+}
+
+impl InputSource for MemorySource {
+    fn input(&self, day: usize) -> Result<String> {
+        self.inputs.get(&day).cloned().ok_or_else(|| {
+            AocError::Io(io::Error::new(
+                ErrorKind::NotFound,
+                format!("no input for day {} in memory", day),
+            ))
+        })
+    }
+}
+
+/// Serves inputs from an [`InputCache`] and falls back to another source on a miss.
+///
+/// Only inputs the inner source returned successfully are written to the cache,
+/// and cached copies that turn out to be a web page are downloaded again.
+#[derive(Debug, Clone)]
+pub struct CachedSource<S> {
+    inner: S,
+    cache: InputCache,
+    refresh: bool,
+}
+
+impl<S: InputSource> CachedSource<S> {
+    pub fn new(inner: S, cache: InputCache) -> Self {
+        Self {
+            inner,
+            cache,
+            refresh: false,
+        }
+    }
+
+    /// Always asks the inner source and overwrites the cached copy.
+    pub fn refreshing(mut self) -> Self {
+        self.refresh = true;
+        self
+    }
+}
+
+impl<S: InputSource> InputSource for CachedSource<S> {
+    fn input(&self, day: usize) -> Result<String> {
+        if !self.refresh {
+            if let Some(input) = self.cache.load(day) {
+                if check_body(day, 200, &input).is_ok() {
+                    return Ok(input);
+                }
+            }
+        }
+
+        let input = self.inner.input(day)?;
+        self.cache.store(day, &input)?;
+        Ok(input)
+    }
+}
+
+/// The default source: the on-disk cache, then the [`Vault`] if a key is set,
+/// then adventofcode.com.
+///
+/// Puzzles unlocking within [`MAX_WAIT`](crate::MAX_WAIT) are waited for.
+/// Set `AOC_REFRESH` to get a [`refreshing_source`] instead.
+pub fn default_source() -> Result<CachedSource<VaultSource<UnlockingSource<HttpSource>>>> {
+    env_source(std::env::var_os("AOC_REFRESH").is_some())
+}
+
+/// Like [`default_source`], but skips both the cached copy and the vault to
+/// download the input again, then overwrites the cached copy.
+pub fn refreshing_source() -> Result<CachedSource<VaultSource<UnlockingSource<HttpSource>>>> {
+    env_source(true)
+}
+
+fn env_source(refresh: bool) -> Result<CachedSource<VaultSource<UnlockingSource<HttpSource>>>> {
+    let http = HttpSource::from_env()?;
+    let year = http.client().year();
+    let vault = if refresh { None } else { Vault::from_env()? };
+    let source = CachedSource::new(
+        VaultSource::new(vault, UnlockingSource::new(http, year)),
+        InputCache::from_env()?,
+    );
+
+    if refresh {
+        Ok(source.refreshing())
+    } else {
+        Ok(source)
+    }
+}
+
+/// Picks a source from `--input <path>` in `args`, then from `AOC_INPUT`.
+///
+/// A path of `-` reads stdin. Without either, [`default_source`] is used.
+pub fn source_from_args<I>(args: I) -> Result<Box<dyn InputSource>>
+where
+    I: IntoIterator<Item = String>,
+{
+    Ok(
+        match input_arg(args).or_else(|| std::env::var("AOC_INPUT").ok()) {
+            Some(path) if path == "-" => Box::new(StdinSource),
+            Some(path) => Box::new(FileSource::new(path)),
+            None => Box::new(default_source()?),
+        },
+    )
+}
+
+fn input_arg<I>(args: I) -> Option<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            return args.next();
+        } else if let Some(path) = arg.strip_prefix("--input=") {
+            return Some(path.to_string());
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_memory_source() {
+    let source = MemorySource::new()
+        .with_input(1, "1 2")
+        .with_input(2, "3 4");
+
+    assert_eq!("1 2", source.input(1).unwrap());
+    assert_eq!("3 4", source.input(2).unwrap());
+}
+
+#[test]
+fn test_file_source() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("day5.txt"), "from dir").unwrap();
+    std::fs::write(dir.path().join("example.txt"), "from file").unwrap();
+
+    assert_eq!("from dir", FileSource::new(dir.path()).input(5).unwrap());
+    assert_eq!(
+        "from file",
+        FileSource::new(dir.path().join("example.txt"))
+            .input(5)
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_cached_source() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+
+    let source = CachedSource::new(MemorySource::new().with_input(1, "fresh"), cache.clone());
+    assert_eq!("fresh", source.input(1).unwrap());
+    assert_eq!(Some("fresh".to_string()), cache.load(1));
+
+    cache.store(1, "cached").unwrap();
+    assert_eq!("cached", source.input(1).unwrap());
+    assert_eq!("fresh", source.refreshing().input(1).unwrap());
+    assert_eq!(Some("fresh".to_string()), cache.load(1));
+}
+
+#[test]
+fn test_cached_source_skips_failures() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+
+    assert!(CachedSource::new(MemorySource::new(), cache.clone())
+        .input(1)
+        .is_err());
+    assert_eq!(None, cache.load(1));
+}
+
+#[test]
+fn test_input_arg() {
+    let args = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(None, input_arg(args(&[])));
+    assert_eq!(Some("a.txt".into()), input_arg(args(&["--input", "a.txt"])));
+    assert_eq!(Some("-".into()), input_arg(args(&["-v", "--input=-"])));
+}
+
+#[cfg(test)]
+fn mock_source(server: &crate::mock::MockServer) -> HttpSource {
+    HttpSource::with_client(
+        AocClient::new(server.url(), 2023)
+            .with_session("abc")
+            .with_retries(0, std::time::Duration::ZERO),
+    )
+}
+
+#[test]
+fn test_http_source() {
+    use crate::mock::{MockResponse, MockServer};
+
+    let server = MockServer::start(|req| {
+        if req.method == "GET"
+            && req.path == "/2023/day/5/input"
+            && req.header("cookie") == Some("session=abc")
+        {
+            MockResponse::ok("47|53\n")
+        } else {
+            MockResponse::new(500, "unexpected request")
+        }
+    });
+
+    assert_eq!("47|53\n", mock_source(&server).input(5).unwrap());
+    assert_eq!(1, server.hits());
+}
+
+#[test]
+fn test_http_source_not_unlocked() {
+    let server = crate::mock::MockServer::fixed(404, "404 Not Found");
+
+    assert!(matches!(
+        mock_source(&server).input(25),
+        Err(AocError::NotUnlocked { day: 25 })
+    ));
+}
+
+#[test]
+fn test_http_source_repeated_request() {
+    let server = crate::mock::MockServer::fixed(
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks! \
+         The calendar countdown is synchronized with the server time; \
+         the link will be enabled on the calendar the instant this puzzle becomes available.",
+    );
+
+    assert!(matches!(
+        mock_source(&server).input(25),
+        Err(AocError::RepeatedRequest)
+    ));
+}
+
+#[test]
+fn test_http_source_login_page() {
+    let server = crate::mock::MockServer::fixed(
+        200,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+    );
+
+    assert!(matches!(
+        mock_source(&server).input(1),
+        Err(AocError::ExpiredSession)
+    ));
+}
+
+#[test]
+fn test_http_source_html_page() {
+    let server = crate::mock::MockServer::fixed(
+        200,
+        "<!DOCTYPE html>\n<html lang=\"en-us\"><head><title>Advent of Code</title></head></html>",
+    );
+
+    assert!(matches!(
+        mock_source(&server).input(1),
+        Err(AocError::HtmlPage)
+    ));
+}
+
+#[test]
+fn test_check_body() {
+    assert!(check_body(1, 200, "3   4\n4   3\n").is_ok());
+    assert!(check_body(1, 200, "<html>\n<body>").is_err());
+    assert!(check_body(1, 200, "  <!doctype html>").is_err());
+    assert!(check_body(1, 500, "<html>oops</html>").is_ok());
+}
+
+#[test]
+fn test_cached_source_skips_html() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = InputCache::new(dir.path());
+    cache.store(1, "<!DOCTYPE html><html></html>").unwrap();
+
+    let source = CachedSource::new(MemorySource::new().with_input(1, "fresh"), cache.clone());
+    assert_eq!("fresh", source.input(1).unwrap());
+    assert_eq!(Some("fresh".to_string()), cache.load(1));
+}
+
+#[test]
+fn test_http_source_bad_session() {
+    let server = crate::mock::MockServer::fixed(400, "Puzzle inputs differ by user.");
+
+    assert!(matches!(
+        mock_source(&server).input(1),
+        Err(AocError::ExpiredSession)
+    ));
+}
+
+#[test]
+fn test_http_source_server_error() {
+    let server = crate::mock::MockServer::fixed(500, "Internal Server Error");
+
+    assert!(matches!(
+        mock_source(&server).input(1),
+        Err(AocError::Http { status: 500, .. })
+    ));
+}
+
+#[test]
+fn test_http_source_unreachable() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let client = AocClient::new(url, 2023)
+        .with_session("abc")
+        .with_retries(0, std::time::Duration::ZERO);
+
+    assert!(matches!(
+        HttpSource::with_client(client).input(1),
+        Err(AocError::Network(_))
+    ));
+}
+
+#[test]
+fn test_memory_source_missing_day() {
+    assert!(matches!(
+        MemorySource::new().input(1),
+        Err(AocError::Io(e)) if e.kind() == ErrorKind::NotFound
+    ));
+}
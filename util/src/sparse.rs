@@ -0,0 +1,211 @@
+use crate::{Grid, Rect, Vec2d};
+use std::collections::HashMap;
+
+/// Cells scattered over an unbounded plane, keyed by [`Vec2d`].
+///
+/// The bounding box of the cells is kept up to date as they are inserted and removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2d, T>,
+    bounds: Rect,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: Rect::EMPTY,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cells of `grid` for which `keep` is true, at the same positions.
+    pub fn from_grid<F>(grid: Grid<T>, mut keep: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        grid.into_iter().filter(|(_, cell)| keep(cell)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The smallest rectangle holding every cell; empty without cells.
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn contains(&self, pos: Vec2d) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Vec2d) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2d) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Sets the cell at `pos`, returning the one it replaces.
+    pub fn insert(&mut self, pos: Vec2d, cell: T) -> Option<T> {
+        self.bounds = self.bounds.union(Rect::from_size(pos, Vec2d::new(1, 1)));
+
+        self.cells.insert(pos, cell)
+    }
+
+    pub fn remove(&mut self, pos: Vec2d) -> Option<T> {
+        let cell = self.cells.remove(&pos)?;
+
+        // Only a cell on the edge can hold the bounding box out.
+        let Rect { min, max } = self.bounds;
+        if pos.x == min.x || pos.y == min.y || pos.x == max.x - 1 || pos.y == max.y - 1 {
+            self.bounds = Rect::covering(self.cells.keys().copied());
+        }
+
+        Some(cell)
+    }
+
+    /// Every position holding a cell, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2d> {
+        let mut positions: Vec<_> = self.cells.keys().copied().collect();
+        positions.sort_by_key(|pos| (pos.y, pos.x));
+
+        positions.into_iter()
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2d, &T)> {
+        self.positions().map(|pos| (pos, &self.cells[&pos]))
+    }
+
+    /// A dense grid of the bounding box, with `fill` where there is no cell.
+    ///
+    /// The grid starts at the top left corner of the box, so the cell at `pos`
+    /// ends up at `pos - self.bounds().min`.
+    pub fn to_grid(&self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let bounds = self.bounds;
+        let mut grid = Grid::new(bounds.width(), bounds.height(), fill);
+
+        for (pos, cell) in &self.cells {
+            grid[*pos - bounds.min] = cell.clone();
+        }
+
+        grid
+    }
+
+    /// Draws the bounding box, one line per row, with `empty` where there is no cell.
+    pub fn render<F>(&self, empty: char, mut f: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut text = String::new();
+
+        for y in self.bounds.min.y..self.bounds.max.y {
+            for x in self.bounds.min.x..self.bounds.max.x {
+                text.push(self.get(Vec2d::new(x, y)).map_or(empty, &mut f));
+            }
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::from_grid(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<(Vec2d, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vec2d, T)>>(iter: I) -> Self {
+        let mut sparse = Self::new();
+        sparse.extend(iter);
+        sparse
+    }
+}
+
+impl<T> Extend<(Vec2d, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Vec2d, T)>>(&mut self, iter: I) {
+        for (pos, cell) in iter {
+            self.insert(pos, cell);
+        }
+    }
+}
+
+#[test]
+fn test_bounds() {
+    let mut sparse = SparseGrid::new();
+    assert_eq!(Rect::EMPTY, sparse.bounds());
+
+    sparse.insert(Vec2d::new(2, -1), 'a');
+    sparse.insert(Vec2d::new(-3, 4), 'b');
+    sparse.insert(Vec2d::new(0, 0), 'c');
+    assert_eq!(
+        Rect::new(Vec2d::new(-3, -1), Vec2d::new(3, 5)),
+        sparse.bounds()
+    );
+
+    assert_eq!(Some('b'), sparse.remove(Vec2d::new(-3, 4)));
+    assert_eq!(None, sparse.remove(Vec2d::new(-3, 4)));
+    assert_eq!(
+        Rect::new(Vec2d::new(0, -1), Vec2d::new(3, 1)),
+        sparse.bounds()
+    );
+
+    sparse.remove(Vec2d::new(2, -1));
+    sparse.remove(Vec2d::new(0, 0));
+    assert!(sparse.is_empty());
+    assert_eq!(Rect::EMPTY, sparse.bounds());
+}
+
+#[test]
+fn test_iter() {
+    let sparse: SparseGrid<_> = [
+        (Vec2d::new(5, 1), 'c'),
+        (Vec2d::new(-1, 1), 'b'),
+        (Vec2d::new(9, -2), 'a'),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!("abc", sparse.iter().map(|(_, ch)| *ch).collect::<String>());
+    assert_eq!(Some(&'b'), sparse.get(Vec2d::new(-1, 1)));
+    assert!(!sparse.contains(Vec2d::new(0, 0)));
+}
+
+#[test]
+fn test_grid_conversions() {
+    let grid = Grid::parse_chars("#..\n.#.\n").unwrap();
+
+    let sparse = SparseGrid::from_grid(grid.clone(), |ch| *ch == '#');
+    assert_eq!(2, sparse.len());
+    assert_eq!(
+        Rect::new(Vec2d::new(0, 0), Vec2d::new(2, 2)),
+        sparse.bounds()
+    );
+    assert_eq!(grid.len(), SparseGrid::from(grid.clone()).len());
+
+    let mut moved: SparseGrid<_> = sparse
+        .iter()
+        .map(|(p, ch)| (p - Vec2d::new(4, 4), *ch))
+        .collect();
+    assert_eq!(Grid::parse_chars("#.\n.#\n").unwrap(), moved.to_grid('.'));
+
+    moved.insert(Vec2d::new(-2, -4), 'x');
+    assert_eq!("#.x\n.#.\n", moved.render('.', |ch| *ch));
+}
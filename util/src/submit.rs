@@ -0,0 +1,238 @@
+use crate::client::AocClient;
+use crate::error::Result;
+use crate::examples::strip_tags;
+use crate::ledger::Ledger;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the previous answer; retry after the duration.
+    RateLimited(Duration),
+    AlreadySolved,
+    /// A response none of the other variants matched, as plain text.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Classifies the HTML page returned by the answer endpoint.
+    pub fn parse(html: &str) -> Self {
+        let text = article_text(html);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else if text.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited(parse_wait(&text).unwrap_or_default())
+        } else if text.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong (too high)"),
+            Verdict::TooLow => write!(f, "wrong (too low)"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(wait) => write!(f, "rate limited ({}s left)", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unknown(text) => write!(f, "unknown response: {}", text),
+        }
+    }
+}
+
+impl AocClient {
+    pub fn submit(&self, day: usize, part: usize, answer: &str) -> Result<Verdict> {
+        let response = self.post_form(
+            &format!("day/{}/answer", day),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+
+        Ok(Verdict::parse(&response.text()?))
+    }
+}
+
+/// Submits `answer` for one part of the day using `AOC_SESSION`.
+///
+/// The answer is checked against the [`Ledger`] first and not sent if it is
+/// already known to be wrong; the verdict is recorded there afterwards.
+pub fn submit_answer<T: Display>(day: usize, part: usize, answer: T) -> Result<Verdict> {
+    let answer = answer.to_string();
+    let mut ledger = Ledger::from_env()?;
+
+    ledger.check(day, part, &answer)?;
+
+    let verdict = AocClient::from_env()?.submit(day, part, &answer)?;
+    ledger.record(day, part, &answer, &verdict)?;
+
+    Ok(verdict)
+}
+
+/// Prints the answer, and submits it too when the binary runs with `--submit`.
+///
+/// `--submit=<part>` only submits that part.
+pub fn report_answer<T: Display>(day: usize, part: usize, answer: T) {
+    println!("{}", answer);
+
+    if submit_requested(std::env::args().skip(1), part) {
+        match submit_answer(day, part, answer) {
+            Ok(verdict) => println!("part {}: {}", part, verdict),
+            Err(e) => eprintln!("part {}: {}", part, e),
+        }
+    }
+}
+
+fn submit_requested<I>(args: I, part: usize) -> bool
+where
+    I: IntoIterator<Item = String>,
+{
+    args.into_iter().any(|arg| {
+        arg == "--submit"
+            || arg
+                .strip_prefix("--submit=")
+                .is_some_and(|p| p == part.to_string())
+    })
+}
+
+/// Text of the `<article>` holding the message, without tags.
+fn article_text(html: &str) -> String {
+    let body = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    strip_tags(body)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses "You have 1m 5s left to wait" into a duration.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+fn page(message: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><body><main><article><p>{}</p></article></main></body></html>",
+        message
+    )
+}
+
+#[test]
+fn test_parse_verdicts() {
+    assert_eq!(
+        Verdict::Correct,
+        Verdict::parse(&page(
+            "That's the right answer! You are one gold star closer to finding the Chief Historian."
+        ))
+    );
+    assert_eq!(
+        Verdict::TooHigh,
+        Verdict::parse(&page(
+            "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+        ))
+    );
+    assert_eq!(
+        Verdict::TooLow,
+        Verdict::parse(&page(
+            "That's not the right answer; your answer is too low. Please wait one minute before trying again."
+        ))
+    );
+    assert_eq!(
+        Verdict::Wrong,
+        Verdict::parse(&page(
+            "That's not the right answer. If you're stuck, make sure you're using the full input data."
+        ))
+    );
+    assert_eq!(
+        Verdict::AlreadySolved,
+        Verdict::parse(&page(
+            "You don't seem to be solving the right level. Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a>"
+        ))
+    );
+    assert_eq!(
+        Verdict::Unknown("Something else".into()),
+        Verdict::parse(&page("Something  else"))
+    );
+}
+
+#[test]
+fn test_parse_rate_limited() {
+    assert_eq!(
+        Verdict::RateLimited(Duration::from_secs(32)),
+        Verdict::parse(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 32s left to wait."
+        ))
+    );
+    assert_eq!(
+        Verdict::RateLimited(Duration::from_secs(65)),
+        Verdict::parse(&page(
+            "You gave an answer too recently. You have 1m 5s left to wait."
+        ))
+    );
+}
+
+#[test]
+fn test_submit_requested() {
+    let args = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert!(!submit_requested(args(&[]), 1));
+    assert!(submit_requested(args(&["--submit"]), 2));
+    assert!(submit_requested(args(&["--submit=2"]), 2));
+    assert!(!submit_requested(args(&["--submit=1"]), 2));
+}
+
+#[test]
+fn test_submit() {
+    use crate::mock::{MockResponse, MockServer};
+
+    let server = MockServer::start(|req| {
+        if req.method == "POST"
+            && req.path == "/2023/day/3/answer"
+            && req.header("cookie") == Some("session=abc")
+            && req.body == "level=1&answer=4%2C6%2C3"
+        {
+            MockResponse::ok(page("That's the right answer!"))
+        } else {
+            MockResponse::ok(page("That's not the right answer."))
+        }
+    });
+    let client = AocClient::new(server.url(), 2023).with_session("abc");
+
+    assert_eq!(Verdict::Correct, client.submit(3, 1, "4,6,3").unwrap());
+    assert_eq!(Verdict::Wrong, client.submit(3, 2, "4,6,3").unwrap());
+}
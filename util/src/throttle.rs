@@ -0,0 +1,115 @@
+use crate::config::Config;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default gap between two requests to the site.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Spaces out requests, across processes, by a minimum interval.
+///
+/// The time of the last request is kept in a lock file that every process
+/// locks while it waits for its turn.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    path: PathBuf,
+    min_interval: Duration,
+}
+
+impl Throttle {
+    pub fn new<P: Into<PathBuf>>(path: P, min_interval: Duration) -> Self {
+        Self {
+            path: path.into(),
+            min_interval,
+        }
+    }
+
+    /// `aoc24-util.lock` next to the config file, shared by every profile of the user.
+    ///
+    /// Without a config directory, it goes in the default input cache instead.
+    pub fn shared() -> Self {
+        let dir = Config::path()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"));
+
+        Self::new(dir.join("aoc24-util.lock"), MIN_INTERVAL)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Blocks until `min_interval` has passed since the last request, then claims the slot.
+    pub fn wait(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        file.lock()?;
+
+        if let Some(next) = read_last(&mut file)?.map(|last| last + self.min_interval) {
+            if let Ok(remaining) = next.duration_since(SystemTime::now()) {
+                std::thread::sleep(remaining);
+            }
+        }
+
+        write_last(&mut file, SystemTime::now())
+        // The lock is released when `file` is closed.
+    }
+}
+
+fn read_last(file: &mut File) -> io::Result<Option<SystemTime>> {
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+
+    Ok(text
+        .trim()
+        .parse()
+        .ok()
+        .map(|millis| UNIX_EPOCH + Duration::from_millis(millis)))
+}
+
+fn write_last(file: &mut File, time: SystemTime) -> io::Result<()> {
+    // Rounded up, so that the next request never comes before the interval is over.
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .div_ceil(1_000_000);
+
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{}", millis)
+}
+
+#[test]
+fn test_throttle() {
+    use std::time::Instant;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("throttle.lock");
+    let start = Instant::now();
+
+    let threads: Vec<_> = (0..3)
+        .map(|_| {
+            let throttle = Throttle::new(&path, Duration::from_millis(100));
+            std::thread::spawn(move || throttle.wait().unwrap())
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
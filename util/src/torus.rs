@@ -0,0 +1,119 @@
+use crate::{Grid, Rect, Vec2d};
+
+/// A plane of `size` that wraps around on both axes, like the screen of old arcade games.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Torus {
+    size: Vec2d,
+}
+
+impl Torus {
+    /// Panics unless both sides are positive.
+    pub fn new(width: isize, height: isize) -> Self {
+        assert!(
+            width > 0 && height > 0,
+            "a torus can't be {}x{}",
+            width,
+            height
+        );
+
+        Self {
+            size: Vec2d::new(width, height),
+        }
+    }
+
+    /// The space the cells of `grid` wrap around in.
+    pub fn of<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width() as isize, grid.height() as isize)
+    }
+
+    pub fn size(&self) -> Vec2d {
+        self.size
+    }
+
+    /// The positions every other one wraps onto.
+    pub fn rect(&self) -> Rect {
+        Rect::from_size(Vec2d::ZERO, self.size)
+    }
+
+    /// The position inside [`Torus::rect`] that `pos` lands on.
+    pub fn wrap(&self, pos: Vec2d) -> Vec2d {
+        pos.rem_euclid(self.size)
+    }
+
+    /// Where something at `pos` moving by `vel` each step is after `t` steps.
+    ///
+    /// Doesn't overflow however large `t` is, and goes backwards for a negative `t`.
+    pub fn advance(&self, pos: Vec2d, vel: Vec2d, t: isize) -> Vec2d {
+        let axis = |pos: isize, vel: isize, size: isize| {
+            let steps = t.rem_euclid(size) as i128;
+
+            (pos as i128 + vel.rem_euclid(size) as i128 * steps).rem_euclid(size as i128) as isize
+        };
+
+        Vec2d::new(
+            axis(pos.x, vel.x, self.size.x),
+            axis(pos.y, vel.y, self.size.y),
+        )
+    }
+
+    /// The four neighbors of `pos`, wrapped around the edges.
+    pub fn neighbors4(&self, pos: Vec2d) -> impl Iterator<Item = Vec2d> + '_ {
+        pos.neighbors4().map(|p| self.wrap(p))
+    }
+
+    /// The cell of `grid` that `pos` wraps onto. Panics if `grid` is smaller than the torus.
+    pub fn get<'a, T>(&self, grid: &'a Grid<T>, pos: Vec2d) -> &'a T {
+        &grid[self.wrap(pos)]
+    }
+}
+
+#[test]
+fn test_wrap() {
+    let torus = Torus::new(11, 7);
+
+    assert_eq!(Vec2d::new(10, 6), torus.wrap(Vec2d::new(-1, -1)));
+    assert_eq!(Vec2d::new(1, 0), torus.wrap(Vec2d::new(-21, 70)));
+    assert_eq!(Rect::new(Vec2d::ZERO, Vec2d::new(11, 7)), torus.rect());
+    assert_eq!(
+        vec![(0, 6), (1, 0), (0, 1), (10, 0)],
+        torus
+            .neighbors4(Vec2d::ZERO)
+            .map(|p| (p.x, p.y))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_advance() {
+    let torus = Torus::new(11, 7);
+    let (pos, vel) = (Vec2d::new(2, 4), Vec2d::new(2, -3));
+
+    let mut stepped = pos;
+    for t in 1..=100 {
+        stepped = torus.wrap(stepped + vel);
+        assert_eq!(stepped, torus.advance(pos, vel, t));
+    }
+
+    // Velocities larger than the torus, and times that would overflow `vel * t`.
+    assert_eq!(
+        torus.advance(pos, vel, 5),
+        torus.advance(pos, vel + torus.size() * 3, 5)
+    );
+    assert_eq!(
+        torus.advance(pos, vel, isize::MAX % 77),
+        torus.advance(pos, vel, isize::MAX)
+    );
+    assert_eq!(
+        pos,
+        torus.advance(torus.advance(pos, vel, 1000), vel, -1000)
+    );
+}
+
+#[test]
+fn test_grid() {
+    let grid = Grid::parse_chars("ab\ncd\n").unwrap();
+    let torus = Torus::of(&grid);
+
+    assert_eq!('d', *torus.get(&grid, Vec2d::new(-1, 3)));
+    assert_eq!('b', *torus.get(&grid, Vec2d::new(5, -4)));
+}
@@ -0,0 +1,296 @@
+use crate::error::{AocError, Result};
+use crate::source::InputSource;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Waits longer than this are refused instead of keeping the binary idle.
+pub const MAX_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// Delays between retries when the input still isn't there right after the unlock.
+const BACKOFF: [Duration; 5] = [
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+    Duration::from_secs(16),
+];
+
+/// How long after the unlock a missing input is put down to the site lagging.
+const GRACE: Duration = Duration::from_secs(60);
+
+/// Source of the current time, so that waiting can be tested without waiting.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// The moment a puzzle unlocks: midnight US Eastern, i.e. 05:00 UTC in December.
+pub fn unlock_time(year: usize, day: usize) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(unlock_ts(year, day) as u64)
+}
+
+/// Time left before the puzzle unlocks, or `None` if it already has.
+pub fn time_until_unlock<C: Clock>(clock: &C, year: usize, day: usize) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(clock.now())
+        .ok()
+        .filter(|d| !d.is_zero())
+}
+
+/// Wraps a source so that puzzles about to unlock are waited for with a countdown.
+///
+/// During the first minute after the unlock, "not unlocked" answers from the inner
+/// source are retried with an exponential backoff, since the site may lag a few
+/// seconds. Later on they are returned right away, since waiting won't make the
+/// input appear. Being told to stop requesting is never retried.
+#[derive(Debug, Clone)]
+pub struct UnlockingSource<S, C = SystemClock> {
+    inner: S,
+    clock: C,
+    year: usize,
+    max_wait: Duration,
+}
+
+impl<S: InputSource> UnlockingSource<S> {
+    pub fn new(inner: S, year: usize) -> Self {
+        Self {
+            inner,
+            clock: SystemClock,
+            year,
+            max_wait: MAX_WAIT,
+        }
+    }
+}
+
+impl<S: InputSource, C: Clock> UnlockingSource<S, C> {
+    pub fn with_clock<D: Clock>(self, clock: D) -> UnlockingSource<S, D> {
+        UnlockingSource {
+            inner: self.inner,
+            clock,
+            year: self.year,
+            max_wait: self.max_wait,
+        }
+    }
+
+    /// Fails right away for puzzles unlocking later than `max_wait` from now.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    fn wait_for_unlock(&self, day: usize) -> Result<()> {
+        let Some(remaining) = time_until_unlock(&self.clock, self.year, day) else {
+            return Ok(());
+        };
+
+        if remaining > self.max_wait {
+            return Err(AocError::NotUnlocked { day });
+        }
+
+        while let Some(remaining) = time_until_unlock(&self.clock, self.year, day) {
+            eprint!(
+                "\rday {} unlocks in {} ",
+                day,
+                format_duration(remaining.as_secs_f64().ceil() as i64)
+            );
+            let _ = std::io::stderr().flush();
+
+            self.clock.sleep(remaining.min(Duration::from_secs(1)));
+        }
+        eprintln!();
+
+        Ok(())
+    }
+}
+
+impl<S: InputSource, C: Clock> InputSource for UnlockingSource<S, C> {
+    fn input(&self, day: usize) -> Result<String> {
+        self.wait_for_unlock(day)?;
+
+        let grace_end = unlock_time(self.year, day) + GRACE;
+        for delay in BACKOFF {
+            match self.inner.input(day) {
+                Err(AocError::NotUnlocked { .. }) if self.clock.now() < grace_end => {
+                    self.clock.sleep(delay)
+                }
+                result => return result,
+            }
+        }
+
+        self.inner.input(day)
+    }
+}
+
+/// Unix timestamp of [`unlock_time`].
+pub(crate) fn unlock_ts(year: usize, day: usize) -> i64 {
+    days_from_civil(year as i64, 12, day as i64) * 86400 + 5 * 3600
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Formats seconds as `h:mm:ss`, with days in front past 24 hours.
+pub(crate) fn format_duration(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let hms = format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{}d {}", days, hms)
+    } else {
+        hms
+    }
+}
+
+#[test]
+fn test_unlock_time() {
+    use crate::mock::FakeClock;
+
+    // 2024-12-01T05:00:00Z
+    assert_eq!(
+        UNIX_EPOCH + Duration::from_secs(1733029200),
+        unlock_time(2024, 1)
+    );
+    // 2023-12-25T05:00:00Z
+    assert_eq!(1703480400, unlock_ts(2023, 25));
+    assert_eq!("1d 0:20:00", format_duration(86400 + 1200));
+
+    let clock = FakeClock::at(unlock_time(2024, 3) - Duration::from_secs(90));
+    assert_eq!(
+        Some(Duration::from_secs(90)),
+        time_until_unlock(&clock, 2024, 3)
+    );
+    assert_eq!(None, time_until_unlock(&clock, 2024, 2));
+}
+
+#[test]
+fn test_waits_and_retries() {
+    use crate::mock::{FakeClock, MockResponse, MockServer};
+    use crate::source::HttpSource;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let calls = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+        if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+            MockResponse::new(404, "404 Not Found")
+        } else {
+            MockResponse::ok("1 2\n")
+        }
+    });
+
+    let unlock = unlock_time(2024, 5);
+    let source = UnlockingSource::new(
+        HttpSource::new(server.url(), 2024).with_session("abc"),
+        2024,
+    )
+    .with_clock(FakeClock::at(unlock - Duration::from_secs(10)));
+
+    assert_eq!("1 2\n", source.input(5).unwrap());
+    assert_eq!(3, server.hits());
+    // Ten seconds of countdown, then backoffs of one and two seconds.
+    assert_eq!(unlock + Duration::from_secs(3), source.clock.now());
+}
+
+#[test]
+fn test_refuses_long_waits() {
+    use crate::mock::FakeClock;
+
+    let server = crate::mock::MockServer::fixed(200, "1 2\n");
+
+    let source = UnlockingSource::new(
+        crate::source::HttpSource::new(server.url(), 2024).with_session("abc"),
+        2024,
+    )
+    .with_clock(FakeClock::at(
+        unlock_time(2024, 5) - Duration::from_secs(2 * 60 * 60),
+    ));
+
+    assert!(matches!(
+        source.input(5),
+        Err(AocError::NotUnlocked { day: 5 })
+    ));
+    assert_eq!(0, server.hits());
+    assert_eq!("1 2\n", source.input(4).unwrap());
+}
+
+#[test]
+fn test_gives_up_after_backoff() {
+    use crate::mock::FakeClock;
+
+    let server = crate::mock::MockServer::fixed(404, "404 Not Found");
+
+    let source = UnlockingSource::new(
+        crate::source::HttpSource::new(server.url(), 2024).with_session("abc"),
+        2024,
+    )
+    .with_clock(FakeClock::at(unlock_time(2024, 5)));
+
+    assert!(matches!(
+        source.input(5),
+        Err(AocError::NotUnlocked { day: 5 })
+    ));
+    assert_eq!(BACKOFF.len() + 1, server.hits());
+}
+
+#[test]
+fn test_no_retries_long_after_unlock() {
+    use crate::mock::{FakeClock, MockServer};
+    use crate::source::HttpSource;
+
+    let server = MockServer::fixed(404, "404 Not Found");
+    let now = unlock_time(2024, 5) + Duration::from_secs(86400);
+    let source = UnlockingSource::new(
+        HttpSource::new(server.url(), 2024).with_session("abc"),
+        2024,
+    )
+    .with_clock(FakeClock::at(now));
+
+    assert!(matches!(
+        source.input(5),
+        Err(AocError::NotUnlocked { day: 5 })
+    ));
+    assert_eq!(1, server.hits());
+    assert_eq!(now, source.clock.now());
+}
+
+#[test]
+fn test_no_retries_when_told_to_stop() {
+    use crate::mock::{FakeClock, MockServer};
+    use crate::source::HttpSource;
+
+    let server = MockServer::fixed(
+        400,
+        "Please don't repeatedly request this endpoint before it unlocks!",
+    );
+    let unlock = unlock_time(2024, 5);
+    let source = UnlockingSource::new(
+        HttpSource::new(server.url(), 2024).with_session("abc"),
+        2024,
+    )
+    .with_clock(FakeClock::at(unlock));
+
+    assert!(matches!(source.input(5), Err(AocError::RepeatedRequest)));
+    assert_eq!(1, server.hits());
+    assert_eq!(unlock, source.clock.now());
+}
@@ -0,0 +1,282 @@
+use crate::cache::{year_from_env, YEAR};
+use crate::config::active_profile;
+use crate::error::{AocError, Result};
+use crate::source::InputSource;
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8] = b"AOCV\x01";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Secret the vault keys are derived from: a passphrase or the contents of a key file.
+#[derive(Clone)]
+pub struct VaultKey {
+    secret: Vec<u8>,
+}
+
+impl std::fmt::Debug for VaultKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("VaultKey(..)")
+    }
+}
+
+impl VaultKey {
+    pub fn from_passphrase<S: AsRef<[u8]>>(passphrase: S) -> Self {
+        Self {
+            secret: passphrase.as_ref().to_vec(),
+        }
+    }
+
+    /// Uses the whole file, without trailing whitespace, as the secret.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut secret = fs::read(path)?;
+        while secret.last().is_some_and(|b| b.is_ascii_whitespace()) {
+            secret.pop();
+        }
+
+        Ok(Self { secret })
+    }
+
+    /// `AOC_VAULT_KEY` as a passphrase, or the file named by `AOC_VAULT_KEY_FILE`.
+    pub fn from_env() -> Result<Option<Self>> {
+        if let Ok(passphrase) = std::env::var("AOC_VAULT_KEY") {
+            return Ok(Some(Self::from_passphrase(passphrase)));
+        }
+
+        match std::env::var_os("AOC_VAULT_KEY_FILE") {
+            Some(path) => Ok(Some(Self::from_file(path)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(&self.secret, salt, &mut key)
+            .map_err(|e| AocError::Vault {
+                path: None,
+                message: e.to_string(),
+            })?;
+
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+}
+
+/// Encrypted inputs meant to be committed, stored as `<year>/day<N>.enc`.
+///
+/// Each file holds a random salt for the key derivation (Argon2), a nonce and the
+/// ChaCha20-Poly1305 ciphertext, authenticated together with its year and day.
+#[derive(Debug, Clone)]
+pub struct Vault {
+    dir: PathBuf,
+    year: usize,
+    key: VaultKey,
+}
+
+impl Vault {
+    pub fn new<P: Into<PathBuf>>(dir: P, key: VaultKey) -> Self {
+        Self {
+            dir: dir.into(),
+            year: YEAR,
+            key,
+        }
+    }
+
+    pub fn with_year(mut self, year: usize) -> Self {
+        self.year = year;
+        self
+    }
+
+    /// Uses `AOC_VAULT_DIR` if set, otherwise `vault/` at the workspace root.
+    ///
+    /// With a profile, inputs are kept in a subdirectory named after it, since
+    /// every account has its own. Returns `None` when no key is configured.
+    pub fn from_env() -> Result<Option<Self>> {
+        let dir = match std::env::var_os("AOC_VAULT_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../vault"),
+        };
+        let dir = match active_profile()? {
+            Some(profile) => dir.join(&profile.name),
+            None => dir,
+        };
+
+        let Some(key) = VaultKey::from_env()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self::new(dir, key).with_year(year_from_env()?)))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    pub fn path(&self, day: usize) -> PathBuf {
+        self.dir
+            .join(self.year.to_string())
+            .join(format!("day{}.enc", day))
+    }
+
+    /// The decrypted input of the day, or `None` if the vault doesn't have it.
+    pub fn open(&self, day: usize) -> Result<Option<String>> {
+        let path = self.path(day);
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let error = |message: &str| AocError::Vault {
+            path: Some(path.clone()),
+            message: message.to_string(),
+        };
+
+        let rest = data
+            .strip_prefix(MAGIC)
+            .filter(|rest| rest.len() >= SALT_LEN + NONCE_LEN)
+            .ok_or_else(|| error("not a vault file"))?;
+        let (salt, rest) = rest.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let plaintext = self
+            .key
+            .cipher(salt)?
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: self.aad(day).as_bytes(),
+                },
+            )
+            .map_err(|_| error("wrong key, or the file was modified"))?;
+
+        String::from_utf8(plaintext)
+            .map(Some)
+            .map_err(|_| error("decrypted input isn't UTF-8"))
+    }
+
+    /// Encrypts `input` into the vault, replacing what was there for the day.
+    pub fn seal(&self, day: usize, input: &str) -> Result<()> {
+        let mut salt = [0; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let ciphertext = self
+            .key
+            .cipher(&salt)?
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: input.as_bytes(),
+                    aad: self.aad(day).as_bytes(),
+                },
+            )
+            .map_err(|_| AocError::Vault {
+                path: Some(self.path(day)),
+                message: "encryption failed".into(),
+            })?;
+
+        let path = self.path(day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, [MAGIC, &salt, &nonce, &ciphertext].concat())?;
+
+        Ok(())
+    }
+
+    /// Binds a file to its day, so that files can't be swapped around.
+    fn aad(&self, day: usize) -> String {
+        format!("{}/day{}", self.year, day)
+    }
+}
+
+/// Serves inputs from a [`Vault`] when there is one, and from another source otherwise.
+#[derive(Debug, Clone)]
+pub struct VaultSource<S> {
+    vault: Option<Vault>,
+    inner: S,
+}
+
+impl<S: InputSource> VaultSource<S> {
+    pub fn new(vault: Option<Vault>, inner: S) -> Self {
+        Self { vault, inner }
+    }
+}
+
+impl<S: InputSource> InputSource for VaultSource<S> {
+    fn input(&self, day: usize) -> Result<String> {
+        if let Some(vault) = &self.vault {
+            if let Some(input) = vault.open(day)? {
+                return Ok(input);
+            }
+        }
+
+        self.inner.input(day)
+    }
+}
+
+#[test]
+fn test_seal_and_open() {
+    let dir = tempfile::tempdir().unwrap();
+    let vault = Vault::new(dir.path(), VaultKey::from_passphrase("hunter2")).with_year(2023);
+
+    assert_eq!(None, vault.open(1).unwrap());
+
+    vault.seal(1, "3   4\n4   3\n").unwrap();
+    assert_eq!(dir.path().join("2023/day1.enc"), vault.path(1));
+    assert_eq!(Some("3   4\n4   3\n".to_string()), vault.open(1).unwrap());
+
+    let data = fs::read(vault.path(1)).unwrap();
+    assert!(!String::from_utf8_lossy(&data).contains("3   4"));
+
+    let wrong = Vault::new(dir.path(), VaultKey::from_passphrase("hunter3")).with_year(2023);
+    assert!(matches!(wrong.open(1), Err(AocError::Vault { .. })));
+
+    // A file moved to another day doesn't authenticate.
+    fs::copy(vault.path(1), vault.path(2)).unwrap();
+    assert!(matches!(vault.open(2), Err(AocError::Vault { .. })));
+}
+
+#[test]
+fn test_key_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("key");
+    fs::write(&path, "hunter2\n").unwrap();
+
+    let sealed = Vault::new(dir.path(), VaultKey::from_passphrase("hunter2"));
+    sealed.seal(5, "input").unwrap();
+
+    let vault = Vault::new(dir.path(), VaultKey::from_file(&path).unwrap());
+    assert_eq!(Some("input".to_string()), vault.open(5).unwrap());
+}
+
+#[test]
+fn test_vault_source() {
+    use crate::source::MemorySource;
+
+    let dir = tempfile::tempdir().unwrap();
+    let vault = Vault::new(dir.path(), VaultKey::from_passphrase("hunter2"));
+    vault.seal(1, "secret").unwrap();
+    let fallback = MemorySource::new()
+        .with_input(1, "fallback 1")
+        .with_input(2, "fallback 2");
+
+    let source = VaultSource::new(Some(vault), fallback.clone());
+    assert_eq!("secret", source.input(1).unwrap());
+    assert_eq!("fallback 2", source.input(2).unwrap());
+
+    let source = VaultSource::new(None, fallback);
+    assert_eq!("fallback 1", source.input(1).unwrap());
+}
@@ -0,0 +1,242 @@
+use crate::dir::{Dir4, Dir8};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// A position or offset on a grid, with `y` growing downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vec2d {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vec2d {
+    pub const ZERO: Vec2d = Vec2d::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn from_tuple(t: (isize, isize)) -> Self {
+        Self::new(t.0, t.1)
+    }
+
+    /// Component-wise remainder that is never negative, for wrapping around a `size`.
+    pub fn rem_euclid(self, size: Vec2d) -> Vec2d {
+        Vec2d::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+    }
+
+    /// Distance moving along the axes only.
+    pub fn manhattan(self, other: Vec2d) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Distance moving diagonally as well, like a king in chess.
+    pub fn chebyshev(self, other: Vec2d) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Turns by 90 degrees clockwise as seen on screen, so north becomes east.
+    pub fn rotate_cw(self) -> Vec2d {
+        Vec2d::new(-self.y, self.x)
+    }
+
+    /// Turns by 90 degrees counterclockwise as seen on screen, so north becomes west.
+    pub fn rotate_ccw(self) -> Vec2d {
+        Vec2d::new(self.y, -self.x)
+    }
+
+    /// Each component reduced to -1, 0 or 1.
+    pub fn signum(self) -> Vec2d {
+        Vec2d::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn dot(self, other: Vec2d) -> isize {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product; zero when the vectors are parallel.
+    pub fn cross(self, other: Vec2d) -> isize {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Vec2d) -> Vec2d {
+        Vec2d::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Vec2d) -> Vec2d {
+        Vec2d::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// The four orthogonal neighbors, clockwise from north.
+    pub fn neighbors4(self) -> impl Iterator<Item = Vec2d> {
+        Dir4::iter().map(move |dir| self + dir.offset())
+    }
+
+    /// The eight neighbors, diagonals included, clockwise from north.
+    pub fn neighbors8(self) -> impl Iterator<Item = Vec2d> {
+        Dir8::iter().map(move |dir| self + dir.offset())
+    }
+}
+
+impl From<(isize, isize)> for Vec2d {
+    fn from(t: (isize, isize)) -> Self {
+        Self::new(t.0, t.1)
+    }
+}
+
+impl Add for Vec2d {
+    type Output = Vec2d;
+
+    fn add(self, other: Vec2d) -> Vec2d {
+        Vec2d {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for Vec2d {
+    type Output = Vec2d;
+
+    fn sub(self, other: Vec2d) -> Vec2d {
+        Vec2d {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl Neg for Vec2d {
+    type Output = Vec2d;
+
+    fn neg(self) -> Vec2d {
+        Vec2d {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl Rem for Vec2d {
+    type Output = Vec2d;
+
+    fn rem(self, other: Vec2d) -> Vec2d {
+        Vec2d {
+            x: self.x % other.x,
+            y: self.y % other.y,
+        }
+    }
+}
+
+impl Mul<isize> for Vec2d {
+    type Output = Vec2d;
+
+    fn mul(self, other: isize) -> Vec2d {
+        Vec2d {
+            x: self.x * other,
+            y: self.y * other,
+        }
+    }
+}
+
+impl Div<isize> for Vec2d {
+    type Output = Vec2d;
+
+    fn div(self, other: isize) -> Vec2d {
+        Vec2d {
+            x: self.x / other,
+            y: self.y / other,
+        }
+    }
+}
+
+impl AddAssign for Vec2d {
+    fn add_assign(&mut self, other: Vec2d) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec2d {
+    fn sub_assign(&mut self, other: Vec2d) {
+        *self = *self - other;
+    }
+}
+
+impl RemAssign for Vec2d {
+    fn rem_assign(&mut self, other: Vec2d) {
+        *self = *self % other;
+    }
+}
+
+impl MulAssign<isize> for Vec2d {
+    fn mul_assign(&mut self, other: isize) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<isize> for Vec2d {
+    fn div_assign(&mut self, other: isize) {
+        *self = *self / other;
+    }
+}
+
+#[test]
+fn test_operators() {
+    let mut v = Vec2d::new(3, -4);
+
+    assert_eq!(Vec2d::new(-3, 4), -v);
+    assert_eq!(Vec2d::new(6, -8), v * 2);
+    assert_eq!(Vec2d::new(1, -2), v / 2);
+    assert_eq!(Vec2d::new(1, -1), v % Vec2d::new(2, 3));
+
+    v += Vec2d::new(1, 1);
+    v *= 3;
+    v -= Vec2d::new(2, 0);
+    v /= 2;
+    assert_eq!(Vec2d::new(5, -4), v);
+    v %= Vec2d::new(3, 3);
+    assert_eq!(Vec2d::new(2, -1), v);
+}
+
+#[test]
+fn test_rem_euclid() {
+    let size = Vec2d::new(11, 7);
+
+    assert_eq!(Vec2d::new(10, 6), Vec2d::new(-1, -1).rem_euclid(size));
+    assert_eq!(Vec2d::new(1, 0), Vec2d::new(12, 14).rem_euclid(size));
+}
+
+#[test]
+fn test_geometry() {
+    let (a, b) = (Vec2d::new(1, 2), Vec2d::new(4, -2));
+
+    assert_eq!(7, a.manhattan(b));
+    assert_eq!(4, a.chebyshev(b));
+    assert_eq!(0, a.dot(b));
+    assert_eq!(5, a.dot(Vec2d::new(1, 2)));
+    assert_eq!(-10, a.cross(b));
+    assert_eq!(0, a.cross(a * 3));
+    assert_eq!(Vec2d::new(1, -1), (b - a).signum());
+    assert_eq!(Vec2d::new(1, -2), a.min(b));
+    assert_eq!(Vec2d::new(4, 2), a.max(b));
+
+    let north = Dir4::North.offset();
+    assert_eq!(Dir4::East.offset(), north.rotate_cw());
+    assert_eq!(Dir4::West.offset(), north.rotate_ccw());
+    assert_eq!(a, a.rotate_cw().rotate_ccw());
+}
+
+#[test]
+fn test_neighbors() {
+    let pos = Vec2d::new(5, 5);
+
+    assert_eq!(
+        vec![(5, 4), (6, 5), (5, 6), (4, 5)],
+        pos.neighbors4().map(|p| (p.x, p.y)).collect::<Vec<_>>()
+    );
+    assert_eq!(8, pos.neighbors8().count());
+    assert!(pos.neighbors8().all(|p| p.chebyshev(pos) == 1));
+}
@@ -0,0 +1,307 @@
+use crate::error::{AocError, Result};
+use crate::vecn::{parse_coords, VecN};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+/// A point or offset in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vec3d {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Vec3d {
+    pub const ZERO: Vec3d = Vec3d::new(0, 0, 0);
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Component-wise remainder that is never negative, for wrapping around a `size`.
+    pub fn rem_euclid(self, size: Vec3d) -> Vec3d {
+        Vec3d::new(
+            self.x.rem_euclid(size.x),
+            self.y.rem_euclid(size.y),
+            self.z.rem_euclid(size.z),
+        )
+    }
+
+    /// Distance moving along the axes only.
+    pub fn manhattan(self, other: Vec3d) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Distance moving diagonally as well.
+    pub fn chebyshev(self, other: Vec3d) -> usize {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// Each component reduced to -1, 0 or 1.
+    pub fn signum(self) -> Vec3d {
+        Vec3d::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    pub fn dot(self, other: Vec3d) -> isize {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Perpendicular to both vectors; zero when they are parallel.
+    pub fn cross(self, other: Vec3d) -> Vec3d {
+        Vec3d::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Vec3d) -> Vec3d {
+        Vec3d::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Vec3d) -> Vec3d {
+        Vec3d::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// The six neighbors sharing a face.
+    pub fn neighbors6(self) -> impl Iterator<Item = Vec3d> {
+        VecN::from(self).axis_neighbors().map(Vec3d::from)
+    }
+
+    /// The 26 neighbors sharing a face, an edge or a corner.
+    pub fn neighbors26(self) -> impl Iterator<Item = Vec3d> {
+        VecN::from(self).neighbors().map(Vec3d::from)
+    }
+
+    /// The smallest and largest corners of the box holding every point, or `None` without points.
+    ///
+    /// Both corners are inside the box, unlike the `max` of a [`Rect`](crate::Rect).
+    pub fn bounding_box<I>(points: I) -> Option<(Vec3d, Vec3d)>
+    where
+        I: IntoIterator<Item = Vec3d>,
+    {
+        VecN::bounding_box(points.into_iter().map(VecN::from))
+            .map(|(min, max)| (min.into(), max.into()))
+    }
+}
+
+/// Parses `x,y,z`, allowing spaces around the numbers.
+impl FromStr for Vec3d {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let [x, y, z] = parse_coords(s)?;
+
+        Ok(Vec3d::new(x, y, z))
+    }
+}
+
+impl From<(isize, isize, isize)> for Vec3d {
+    fn from(t: (isize, isize, isize)) -> Self {
+        Self::new(t.0, t.1, t.2)
+    }
+}
+
+impl From<VecN<3>> for Vec3d {
+    fn from(v: VecN<3>) -> Self {
+        let [x, y, z] = v.0;
+
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Vec3d> for VecN<3> {
+    fn from(v: Vec3d) -> Self {
+        VecN([v.x, v.y, v.z])
+    }
+}
+
+impl Add for Vec3d {
+    type Output = Vec3d;
+
+    fn add(self, other: Vec3d) -> Vec3d {
+        Vec3d {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for Vec3d {
+    type Output = Vec3d;
+
+    fn sub(self, other: Vec3d) -> Vec3d {
+        Vec3d {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl Neg for Vec3d {
+    type Output = Vec3d;
+
+    fn neg(self) -> Vec3d {
+        Vec3d {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl Rem for Vec3d {
+    type Output = Vec3d;
+
+    fn rem(self, other: Vec3d) -> Vec3d {
+        Vec3d {
+            x: self.x % other.x,
+            y: self.y % other.y,
+            z: self.z % other.z,
+        }
+    }
+}
+
+impl Mul<isize> for Vec3d {
+    type Output = Vec3d;
+
+    fn mul(self, other: isize) -> Vec3d {
+        Vec3d {
+            x: self.x * other,
+            y: self.y * other,
+            z: self.z * other,
+        }
+    }
+}
+
+impl Div<isize> for Vec3d {
+    type Output = Vec3d;
+
+    fn div(self, other: isize) -> Vec3d {
+        Vec3d {
+            x: self.x / other,
+            y: self.y / other,
+            z: self.z / other,
+        }
+    }
+}
+
+impl AddAssign for Vec3d {
+    fn add_assign(&mut self, other: Vec3d) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec3d {
+    fn sub_assign(&mut self, other: Vec3d) {
+        *self = *self - other;
+    }
+}
+
+impl RemAssign for Vec3d {
+    fn rem_assign(&mut self, other: Vec3d) {
+        *self = *self % other;
+    }
+}
+
+impl MulAssign<isize> for Vec3d {
+    fn mul_assign(&mut self, other: isize) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign<isize> for Vec3d {
+    fn div_assign(&mut self, other: isize) {
+        *self = *self / other;
+    }
+}
+
+#[test]
+fn test_operators() {
+    let mut v = Vec3d::new(3, -4, 1);
+
+    assert_eq!(Vec3d::new(-3, 4, -1), -v);
+    assert_eq!(Vec3d::new(6, -8, 2), v * 2);
+    assert_eq!(Vec3d::new(1, -2, 0), v / 2);
+    assert_eq!(Vec3d::new(1, -1, 1), v % Vec3d::new(2, 3, 2));
+
+    v += Vec3d::new(1, 1, 1);
+    v *= 3;
+    v -= Vec3d::new(2, 0, 0);
+    v /= 2;
+    assert_eq!(Vec3d::new(5, -4, 3), v);
+    v %= Vec3d::new(3, 3, 3);
+    assert_eq!(Vec3d::new(2, -1, 0), v);
+}
+
+#[test]
+fn test_geometry() {
+    let (a, b) = (Vec3d::new(1, 2, 3), Vec3d::new(4, -2, 3));
+
+    assert_eq!(
+        Vec3d::new(1, 1, 3),
+        Vec3d::new(-3, -5, 3).rem_euclid(Vec3d::new(4, 3, 5))
+    );
+    assert_eq!(7, a.manhattan(b));
+    assert_eq!(4, a.chebyshev(b));
+    assert_eq!(9, a.dot(b));
+    assert_eq!(Vec3d::new(12, 9, -10), a.cross(b));
+    assert_eq!(0, a.cross(b).dot(a));
+    assert_eq!(Vec3d::new(1, -1, 0), (b - a).signum());
+    assert_eq!(Vec3d::new(1, -2, 3), a.min(b));
+    assert_eq!(Vec3d::new(4, 2, 3), a.max(b));
+}
+
+#[test]
+fn test_neighbors() {
+    let p = Vec3d::new(5, 5, 5);
+
+    assert_eq!(6, p.neighbors6().count());
+    assert!(p.neighbors6().all(|n| n.manhattan(p) == 1));
+    assert_eq!(26, p.neighbors26().count());
+    assert!(p.neighbors26().all(|n| n.chebyshev(p) == 1));
+}
+
+#[test]
+fn test_bounding_box() {
+    let points = [
+        Vec3d::new(1, 5, 0),
+        Vec3d::new(-2, 3, 2),
+        Vec3d::new(4, 4, -1),
+    ];
+
+    assert_eq!(
+        Some((Vec3d::new(-2, 3, -1), Vec3d::new(4, 5, 2))),
+        Vec3d::bounding_box(points)
+    );
+    assert_eq!(None, Vec3d::bounding_box([]));
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(Vec3d::new(19, -13, 30), "19, -13, 30".parse().unwrap());
+
+    match "1,2,z".parse::<Vec3d>() {
+        Err(AocError::Parse { column, token, .. }) => {
+            assert_eq!((5, "z"), (column, token.as_str()))
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
@@ -0,0 +1,315 @@
+use crate::error::{AocError, Result};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+    SubAssign,
+};
+use std::str::FromStr;
+
+/// A point or offset with `N` integer coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VecN<const N: usize>(pub [isize; N]);
+
+impl<const N: usize> VecN<N> {
+    pub const ZERO: Self = Self([0; N]);
+
+    pub fn new(coords: [isize; N]) -> Self {
+        Self(coords)
+    }
+
+    /// The offset of one step along `axis`, in the positive direction.
+    pub fn unit(axis: usize) -> Self {
+        let mut unit = Self::ZERO;
+        unit.0[axis] = 1;
+        unit
+    }
+
+    fn zip_with<F>(self, other: Self, mut f: F) -> Self
+    where
+        F: FnMut(isize, isize) -> isize,
+    {
+        Self(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    fn map<F>(self, f: F) -> Self
+    where
+        F: FnMut(isize) -> isize,
+    {
+        Self(self.0.map(f))
+    }
+
+    /// Component-wise remainder that is never negative, for wrapping around a `size`.
+    pub fn rem_euclid(self, size: Self) -> Self {
+        self.zip_with(size, isize::rem_euclid)
+    }
+
+    /// Distance moving along the axes only.
+    pub fn manhattan(self, other: Self) -> usize {
+        (0..N).map(|i| self.0[i].abs_diff(other.0[i])).sum()
+    }
+
+    /// Distance moving diagonally as well.
+    pub fn chebyshev(self, other: Self) -> usize {
+        (0..N)
+            .map(|i| self.0[i].abs_diff(other.0[i]))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Each component reduced to -1, 0 or 1.
+    pub fn signum(self) -> Self {
+        self.map(isize::signum)
+    }
+
+    pub fn dot(self, other: Self) -> isize {
+        (0..N).map(|i| self.0[i] * other.0[i]).sum()
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Self) -> Self {
+        self.zip_with(other, isize::min)
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Self) -> Self {
+        self.zip_with(other, isize::max)
+    }
+
+    /// The `2 * N` neighbors one step along a single axis.
+    pub fn axis_neighbors(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| [self - Self::unit(axis), self + Self::unit(axis)])
+    }
+
+    /// The `3^N - 1` neighbors touching this point, diagonals included.
+    pub fn neighbors(self) -> impl Iterator<Item = Self> {
+        (0..3usize.pow(N as u32)).filter_map(move |mut index| {
+            let offset = Self(std::array::from_fn(|_| {
+                let digit = index % 3;
+                index /= 3;
+                digit as isize - 1
+            }));
+
+            (offset != Self::ZERO).then(|| self + offset)
+        })
+    }
+
+    /// The smallest and largest corners of the box holding every point, or `None` without points.
+    ///
+    /// Both corners are inside the box, unlike the `max` of a [`Rect`](crate::Rect).
+    pub fn bounding_box<I>(points: I) -> Option<(Self, Self)>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => Some((min.min(p), max.max(p))),
+        })
+    }
+}
+
+/// Parses `N` comma-separated integers, such as `1,-2,3`, allowing spaces around them.
+pub(crate) fn parse_coords<const N: usize>(text: &str) -> Result<[isize; N]> {
+    let error = |token: &str| {
+        let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+
+        AocError::Parse {
+            line: 1,
+            column: text[..offset].chars().count() + 1,
+            token: token.to_string(),
+        }
+    };
+
+    let mut coords = [0; N];
+    let mut tokens = text.trim().split(',').map(str::trim);
+
+    for coord in &mut coords {
+        let token = tokens.next().ok_or_else(|| error(&text[text.len()..]))?;
+        *coord = token.parse().map_err(|_| error(token))?;
+    }
+
+    match tokens.next() {
+        Some(token) => Err(error(token)),
+        None => Ok(coords),
+    }
+}
+
+impl<const N: usize> FromStr for VecN<N> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_coords(s).map(Self)
+    }
+}
+
+impl<const N: usize> From<[isize; N]> for VecN<N> {
+    fn from(coords: [isize; N]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<const N: usize> Index<usize> for VecN<N> {
+    type Output = isize;
+
+    fn index(&self, axis: usize) -> &isize {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for VecN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut isize {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for VecN<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for VecN<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> Neg for VecN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|a| -a)
+    }
+}
+
+impl<const N: usize> Rem for VecN<N> {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a % b)
+    }
+}
+
+impl<const N: usize> Mul<isize> for VecN<N> {
+    type Output = Self;
+
+    fn mul(self, other: isize) -> Self {
+        self.map(|a| a * other)
+    }
+}
+
+impl<const N: usize> Div<isize> for VecN<N> {
+    type Output = Self;
+
+    fn div(self, other: isize) -> Self {
+        self.map(|a| a / other)
+    }
+}
+
+impl<const N: usize> AddAssign for VecN<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: usize> SubAssign for VecN<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const N: usize> RemAssign for VecN<N> {
+    fn rem_assign(&mut self, other: Self) {
+        *self = *self % other;
+    }
+}
+
+impl<const N: usize> MulAssign<isize> for VecN<N> {
+    fn mul_assign(&mut self, other: isize) {
+        *self = *self * other;
+    }
+}
+
+impl<const N: usize> DivAssign<isize> for VecN<N> {
+    fn div_assign(&mut self, other: isize) {
+        *self = *self / other;
+    }
+}
+
+#[test]
+fn test_operators() {
+    let mut v = VecN([3, -4, 1, 0]);
+
+    assert_eq!(VecN([-3, 4, -1, 0]), -v);
+    assert_eq!(VecN([6, -8, 2, 0]), v * 2);
+    assert_eq!(VecN([1, -2, 0, 0]), v / 2);
+    assert_eq!(VecN([1, -1, 1, 0]), v % VecN([2, 3, 2, 2]));
+    assert_eq!(VecN([4, -4, 1, 1]), v + VecN::unit(0) + VecN::unit(3));
+
+    v += VecN([1, 1, 1, 1]);
+    v *= 3;
+    v -= VecN([2, 0, 0, 0]);
+    v /= 2;
+    assert_eq!(VecN([5, -4, 3, 1]), v);
+    v %= VecN([3, 3, 3, 3]);
+    assert_eq!(VecN([2, -1, 0, 1]), v);
+
+    v[2] = 7;
+    assert_eq!(7, v[2]);
+}
+
+#[test]
+fn test_geometry() {
+    let (a, b) = (VecN([1, 2, 3, 4]), VecN([4, -2, 3, 0]));
+
+    assert_eq!(
+        VecN([1, 1, 3, 4]),
+        VecN([-3, -5, 3, 4]).rem_euclid(VecN([4, 3, 5, 5]))
+    );
+    assert_eq!(11, a.manhattan(b));
+    assert_eq!(4, a.chebyshev(b));
+    assert_eq!(9, a.dot(b));
+    assert_eq!(VecN([1, -1, 0, -1]), (b - a).signum());
+    assert_eq!(VecN([1, -2, 3, 0]), a.min(b));
+    assert_eq!(VecN([4, 2, 3, 4]), a.max(b));
+}
+
+#[test]
+fn test_neighbors() {
+    let p = VecN([5, 5, 5, 5]);
+
+    assert_eq!(8, p.axis_neighbors().count());
+    assert!(p.axis_neighbors().all(|n| n.manhattan(p) == 1));
+    assert_eq!(80, p.neighbors().count());
+    assert!(p.neighbors().all(|n| n.chebyshev(p) == 1));
+    assert_eq!(2, VecN([0]).neighbors().count());
+}
+
+#[test]
+fn test_bounding_box() {
+    let points = [VecN([1, 5]), VecN([-2, 3]), VecN([4, 4])];
+
+    assert_eq!(
+        Some((VecN([-2, 3]), VecN([4, 5]))),
+        VecN::bounding_box(points)
+    );
+    assert_eq!(None, VecN::<2>::bounding_box([]));
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(VecN([1, -2, 30, 4]), "1,-2, 30 ,4\n".parse().unwrap());
+
+    for (text, column, token) in [("1,x,3", 3, "x"), ("1,2", 4, ""), ("1,2,3,4", 7, "4")] {
+        match text.parse::<VecN<3>>() {
+            Err(AocError::Parse {
+                line: 1,
+                column: c,
+                token: t,
+            }) => assert_eq!((column, token), (c, t.as_str())),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}